@@ -0,0 +1,165 @@
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+use crate::consts::*;
+
+pub const LYNX_RSA_BYTES: usize = 51;
+pub const HLE_LOAD_ADDRESS: u16 = 0x0200;
+const DECRYPTED_BLOCK_MARKER: u8 = 0x15;
+
+/* Public modulus of the retail carts key, most significant byte first. The public exponent is 3. */
+const LYNX_PUBLIC_MODULUS: [u8; LYNX_RSA_BYTES] = [
+    0x35, 0xB5, 0xA3, 0x94, 0x28, 0x06, 0xD8, 0xA2, 0x26, 0x95, 0xD7, 0x71, 0xB2, 0x3C, 0xFD, 0x56,
+    0x1C, 0x4A, 0x19, 0xB6, 0xA3, 0xB0, 0x26, 0x00, 0x36, 0x5A, 0x30, 0x6E, 0x3C, 0x4D, 0x63, 0x38,
+    0x1B, 0xD4, 0x1C, 0x13, 0x64, 0x89, 0x36, 0x4C, 0xF2, 0xBA, 0x2A, 0x58, 0xF4, 0xFE, 0xE1, 0xFD,
+    0xAC, 0x7E, 0x79,
+];
+
+/* Mikey registers as set by the boot ROM before it reads the cart. */
+pub const BOOT_MIKEY_REGISTERS: [(u16, u8); 16] = [
+    (TIM0BKUP, 0x9E),
+    (TIM0CTLA, 0x18),
+    (TIM2BKUP, 0x68),
+    (TIM2CTLA, 0x1F),
+    (AUD0VOL, 0x00),
+    (AUD1VOL, 0x00),
+    (AUD2VOL, 0x00),
+    (AUD3VOL, 0x00),
+    (MPAN, 0x00),
+    (MSTEREO, 0xFF),
+    (IODIR, 0x1A),
+    (IODAT, 0x0B),
+    (SERCTL, 0x04),
+    (DISPCTL, 0x0D),
+    (PBKUP, 0x29),
+    (SYSCTL1, 0x0A),
+];
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum BootMode {
+    /* Runs the boot ROM from its reset vector. */
+    #[default]
+    Rom,
+    /* Skips the boot ROM: the cart loader is decrypted and started directly. */
+    Hle,
+}
+
+type RsaNumber = [u8; LYNX_RSA_BYTES];
+
+/* Least significant byte first from here on. */
+fn modulus() -> RsaNumber {
+    let mut n = LYNX_PUBLIC_MODULUS;
+    n.reverse();
+    n
+}
+
+fn greater_or_equal(x: &RsaNumber, y: &RsaNumber) -> bool {
+    for i in (0..LYNX_RSA_BYTES).rev() {
+        if x[i] != y[i] {
+            return x[i] > y[i];
+        }
+    }
+    true
+}
+
+/* x = (x + y) mod n, with x and y lower than n. */
+fn add_mod(x: &mut RsaNumber, y: &RsaNumber, n: &RsaNumber) {
+    let mut carry = 0u16;
+    for i in 0..LYNX_RSA_BYTES {
+        let sum = x[i] as u16 + y[i] as u16 + carry;
+        x[i] = sum as u8;
+        carry = sum >> 8;
+    }
+    if carry != 0 || greater_or_equal(x, n) {
+        let mut borrow = 0i16;
+        for i in 0..LYNX_RSA_BYTES {
+            let diff = x[i] as i16 - n[i] as i16 - borrow;
+            x[i] = diff as u8;
+            borrow = (diff < 0) as i16;
+        }
+    }
+}
+
+fn mul_mod(a: &RsaNumber, b: &RsaNumber, n: &RsaNumber) -> RsaNumber {
+    let mut r = [0; LYNX_RSA_BYTES];
+    for i in (0..LYNX_RSA_BYTES).rev() {
+        for bit in (0..8).rev() {
+            let double = r;
+            add_mod(&mut r, &double, n);
+            if b[i] & (1 << bit) != 0 {
+                add_mod(&mut r, a, n);
+            }
+        }
+    }
+    r
+}
+
+/* Size of the encrypted frame announced by its first byte. */
+pub fn encrypted_frame_len(first: u8) -> usize {
+    1 + LYNX_RSA_BYTES * (0x100 - first as usize)
+}
+
+/* Decrypts the loader at the start of a retail cart. Each block of 51 bytes is raised to the power 3,
+its top byte must be 0x15 and the 50 others, from the least significant one, are a running sum of the
+plain bytes. */
+pub fn decrypt_frame(frame: &[u8]) -> Result<Vec<u8>, &'static str> {
+    if frame.is_empty() || frame.len() < encrypted_frame_len(frame[0]) {
+        return Err("Truncated encrypted loader.");
+    }
+    let n = modulus();
+    let mut accumulator = 0u8;
+    let mut plain = Vec::with_capacity((0x100 - frame[0] as usize) * (LYNX_RSA_BYTES - 1));
+
+    for block in frame[1..encrypted_frame_len(frame[0])].chunks_exact(LYNX_RSA_BYTES) {
+        let mut c = [0; LYNX_RSA_BYTES];
+        c.copy_from_slice(block);
+        if greater_or_equal(&c, &n) {
+            return Err("Invalid encrypted loader.");
+        }
+        let m = mul_mod(&mul_mod(&c, &c, &n), &c, &n);
+        if m[LYNX_RSA_BYTES - 1] != DECRYPTED_BLOCK_MARKER {
+            return Err("Invalid encrypted loader.");
+        }
+        for b in &m[..LYNX_RSA_BYTES - 1] {
+            accumulator = accumulator.wrapping_add(*b);
+            plain.push(accumulator);
+        }
+    }
+    Ok(plain)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cartridge::BLL_LOADER;
+
+    #[test]
+    fn decrypt_bll_loader() {
+        let plain = decrypt_frame(&BLL_LOADER).unwrap();
+        assert_eq!(plain.len(), 50);
+        // TXS, LDX #$1F, STZ $FDA0,X ...
+        assert_eq!(plain[..6], [0x9A, 0xA2, 0x1F, 0x9E, 0xA0, 0xFD]);
+        assert_eq!(plain[44..], [0xD0, 0xE3, 0xA2, 0x42, 0x42, 0x00]);
+    }
+
+    #[test]
+    fn decrypt_errors() {
+        assert!(decrypt_frame(&BLL_LOADER[..40]).is_err());
+        let mut corrupted = BLL_LOADER;
+        corrupted[10] ^= 0x01;
+        assert_eq!(decrypt_frame(&corrupted), Err("Invalid encrypted loader."));
+    }
+
+    #[test]
+    fn modular_arithmetic() {
+        let n = modulus();
+        let mut minus_one = n;
+        minus_one[0] -= 1;
+        let mut one = [0; LYNX_RSA_BYTES];
+        one[0] = 1;
+        // (n - 1)^2 = 1 mod n
+        assert_eq!(mul_mod(&minus_one, &minus_one, &n), one);
+        let mut x = minus_one;
+        add_mod(&mut x, &one, &n);
+        assert_eq!(x, [0; LYNX_RSA_BYTES]);
+    }
+}
@@ -7,8 +7,6 @@ pub enum BusStatus {
     PeekCart1,
     PokeCart0,
     PokeCart1,
-    PeekIncCartRipple,
-    PokeIncCartRipple,
     PeekCore,
     PokeCore,
     Peek,
@@ -18,7 +16,7 @@ pub enum BusStatus {
     PokeDone,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Bus {
     data: u8,
     addr: u16,
@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+
+pub const BS93_HEADER_LENGTH: usize = 10;
+
+/* Header of the BLL `.o` executables: magic 0x80 0x08, big endian load address, big endian length
+(header included) and "BS93". The loaders drop the header, the program's first byte lands at the load address. */
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct BS93Header {
+    load_address: u16,
+    length: u16,
+}
+
+impl BS93Header {
+    pub fn is_bs93(file_content: &[u8]) -> bool {
+        file_content.len() > BS93_HEADER_LENGTH &&
+        &file_content[6..=9] == b"BS93"
+    }
+
+    pub fn from_slice(file_content: &[u8]) -> Result<Self, &'static str> {
+        if !Self::is_bs93(file_content) {
+            return Err("Not a BS93 file.");
+        }
+        let header = Self {
+            load_address: u16::from_be_bytes([file_content[2], file_content[3]]),
+            length: u16::from_be_bytes([file_content[4], file_content[5]]),
+        };
+        if (header.length as usize) < BS93_HEADER_LENGTH {
+            return Err("Invalid BS93 length.");
+        }
+        if file_content.len() < header.length as usize {
+            return Err("Truncated BS93 file.");
+        }
+        Ok(header)
+    }
+
+    pub fn load_address(&self) -> u16 {
+        self.load_address
+    }
+
+    pub fn length(&self) -> u16 {
+        self.length
+    }
+}
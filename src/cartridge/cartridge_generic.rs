@@ -1,4 +1,4 @@
-use alloc::vec::Vec;
+use alloc::{sync::Arc, vec::Vec};
 use consts::*;
 use log::trace;
 use serde::{Deserialize, Serialize};
@@ -11,11 +11,12 @@ pub const _256K_PINS: [u32; 16] = [CART_PIN_A0,CART_PIN_A1,CART_PIN_A2,CART_PIN_
 pub const _128K_PINS: [u32; 16] = [CART_PIN_A0,CART_PIN_A1,CART_PIN_A2,CART_PIN_A3,CART_PIN_A4,CART_PIN_A5,CART_PIN_A6,CART_PIN_A7,CART_PIN_A8,0,0,0,0,0,0,0];
 pub const BLOCK_PINS: [u32; 8] = [CART_PIN_A12,CART_PIN_A13,CART_PIN_A14,CART_PIN_A15,CART_PIN_A16,CART_PIN_A17,CART_PIN_A18,CART_PIN_A19];
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct CartridgeGeneric {
     pins: u32,
+    /* The image is shared between clones of the cart, it's only copied when written to. */
     #[serde(skip)]
-    banks: Vec<Vec<u8>>,
+    banks: Vec<Arc<Vec<u8>>>,
     addr_pins: Vec<u32>,
     block_pins: Vec<u32>,
     bank_size: u32,
@@ -57,7 +58,7 @@ impl CartridgeGeneric {
     fn write(&mut self, pins: u32) -> u32 {
         let addr = self.data_address(pins);
         let data = read_pins_u8(pins, DATA_PINS.as_ref());
-        self.banks[0][addr] = data;
+        Arc::make_mut(&mut self.banks[0])[addr] = data;
         trace!("Write 0x{:06x} data:0x{:02x}", addr, data);
         pins
     }
@@ -65,12 +66,16 @@ impl CartridgeGeneric {
     pub fn copy_from(&mut self, other: &CartridgeGeneric) {
         self.banks = other.banks.clone();
     }
+
+    pub fn shares_image_with(&self, other: &CartridgeGeneric) -> bool {
+        self.banks.len() == other.banks.len() && self.banks.iter().zip(&other.banks).all(|(a, b)| Arc::ptr_eq(a, b))
+    }
 }
 
 impl CartridgeI for CartridgeGeneric {
     fn load(&mut self, file_content: &[u8]){
         self.banks.clear();
-        self.banks.push(file_content.to_vec());
+        self.banks.push(Arc::new(file_content.to_vec()));
     }
 
     fn set_pins(&mut self, mut pins: u32) {
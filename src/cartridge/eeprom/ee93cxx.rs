@@ -18,7 +18,7 @@ pub enum Ee93cxxType {
     C86x16,
 }
 
-#[derive(Serialize, Deserialize, PartialEq)]
+#[derive(Clone, Serialize, Deserialize, PartialEq)]
 pub enum Ee93cxxState {
     WaitForStartBit,
     WaitForCommand,
@@ -47,7 +47,7 @@ bitflags! {
     }
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Ee93cxxConf {
     size: usize,
     address_bits: u8,
@@ -93,7 +93,7 @@ impl Ee93cxxConf {
     }
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Ee93cxx {
     data: Vec<u16>,
     state: Ee93cxxState,
@@ -3,7 +3,7 @@ mod ee93cxx;
 use serde::{Deserialize, Serialize};
 use ee93cxx::Ee93cxx;
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub enum EEpromType {
     Ee93c46x8,
     Ee93c56x8,
@@ -17,12 +17,12 @@ pub enum EEpromType {
     Ee93c86x16,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub enum EepromI {
     EE93CXX(Ee93cxx),
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Eeprom {
     eeprom: EepromI,
 }
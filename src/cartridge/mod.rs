@@ -1,9 +1,11 @@
 pub mod lnx_header;
+pub mod bs93_header;
 mod cartridge_generic;
 mod eeprom;
 mod no_intro;
 
 use alloc::{string::String, vec::Vec};
+use bs93_header::BS93Header;
 use bus::{Bus, BusStatus};
 use cartridge_generic::*;
 use consts::*;
@@ -17,7 +19,6 @@ use suzy::registers::Switches;
 use crate::*;
 
 const LNX_HEADER_LENGTH: usize = 64;
-const BS93_HEADER_LENGTH: usize = 10;
 
 const DATA_PINS: [u32; 8] = [CART_PIN_D0, CART_PIN_D1, CART_PIN_D2, CART_PIN_D3, CART_PIN_D4, CART_PIN_D5, CART_PIN_D6, CART_PIN_D7];
 const RIPPLE_PINS: [u32; 11] = [CART_PIN_A0, CART_PIN_A1, CART_PIN_A2, CART_PIN_A3, CART_PIN_A4, CART_PIN_A5, CART_PIN_A6, CART_PIN_A7, CART_PIN_A8, CART_PIN_A9, CART_PIN_A10];
@@ -29,7 +30,7 @@ const _512K: usize = _256K * 2;
 const _1024K: usize = _512K * 2;
 
 // Courtesy of https://github.com/42Bastian/new_bll/
-const BLL_LOADER: [u8; 246] = [0xFF, 0x4A, 0x37, 0xB2, 0xB3, 0x0D, 0xEF, 0x61, 0x56, 0xAB, 0xD3, 0xC3, 0x5D, 0x4B, 0xDE, 0xB8,0x38, 0x17, 0x92, 0x59, 0xFA, 0x40, 0xB1, 0x58, 0xC4, 0x8F, 0xB6, 0x6D, 0xBE, 0xBB, 0x20, 0x8E,0x8A, 0x69, 0x86, 0x6C, 0x18, 0x12, 0x0C, 0x7C, 0x50, 0xCD, 0xAA, 0x63, 0x41, 0x3F, 0xD3, 0x89,0xAD, 0xAB, 0x37, 0x14, 0x01, 0xAD, 0xC5, 0x02, 0x49, 0xFF, 0x85, 0xF1, 0xAD, 0xC6, 0x02, 0x49,0xFF, 0x85, 0xF0, 0xAD, 0xC3, 0x02, 0x85, 0xF3, 0x85, 0xF5, 0xAD, 0xC4, 0x02, 0x85, 0xF2, 0x85,0xF4, 0xA2, 0xC0, 0x9A, 0xA0, 0x29, 0xB9, 0x2D, 0x02, 0x99, 0xC0, 0x01, 0x88, 0xD0, 0xF7, 0xA2,0x03, 0x80, 0x9F, 0xCA, 0xD0, 0x09, 0xE6, 0x00, 0xA5, 0x00, 0x20, 0x00, 0xFE, 0xA2, 0x04, 0xAD,0xB2, 0xFC, 0x92, 0xF2, 0xE6, 0xF2, 0xD0, 0x02, 0xE6, 0xF3, 0xE6, 0xF0, 0xD0, 0x07, 0xE6, 0xF1,0xD0, 0x03, 0x6C, 0xF4, 0x00, 0xC8, 0xD0, 0xE7, 0x80, 0xD9, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+pub(crate) const BLL_LOADER: [u8; 246] = [0xFF, 0x4A, 0x37, 0xB2, 0xB3, 0x0D, 0xEF, 0x61, 0x56, 0xAB, 0xD3, 0xC3, 0x5D, 0x4B, 0xDE, 0xB8,0x38, 0x17, 0x92, 0x59, 0xFA, 0x40, 0xB1, 0x58, 0xC4, 0x8F, 0xB6, 0x6D, 0xBE, 0xBB, 0x20, 0x8E,0x8A, 0x69, 0x86, 0x6C, 0x18, 0x12, 0x0C, 0x7C, 0x50, 0xCD, 0xAA, 0x63, 0x41, 0x3F, 0xD3, 0x89,0xAD, 0xAB, 0x37, 0x14, 0x01, 0xAD, 0xC5, 0x02, 0x49, 0xFF, 0x85, 0xF1, 0xAD, 0xC6, 0x02, 0x49,0xFF, 0x85, 0xF0, 0xAD, 0xC3, 0x02, 0x85, 0xF3, 0x85, 0xF5, 0xAD, 0xC4, 0x02, 0x85, 0xF2, 0x85,0xF4, 0xA2, 0xC0, 0x9A, 0xA0, 0x29, 0xB9, 0x2D, 0x02, 0x99, 0xC0, 0x01, 0x88, 0xD0, 0xF7, 0xA2,0x03, 0x80, 0x9F, 0xCA, 0xD0, 0x09, 0xE6, 0x00, 0xA5, 0x00, 0x20, 0x00, 0xFE, 0xA2, 0x04, 0xAD,0xB2, 0xFC, 0x92, 0xF2, 0xE6, 0xF2, 0xD0, 0x02, 0xE6, 0xF3, 0xE6, 0xF0, 0xD0, 0x07, 0xE6, 0xF1,0xD0, 0x03, 0x6C, 0xF4, 0x00, 0xC8, 0xD0, 0xE7, 0x80, 0xD9, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
 
 #[macro_export]
 macro_rules! TO_U16 {
@@ -94,13 +95,13 @@ pub trait CartridgeI {
     fn pins(&self) -> u32;
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 enum CartType {
     None(),
     Generic(CartridgeGeneric),
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Cartridge {
     ticks_to_done: u8,
     #[serde(skip)]
@@ -129,7 +130,7 @@ impl Cartridge {
         if cart.is_lnx(data) {
             cart.lnx(data);
         } else if cart.is_bs93(data) {
-            cart.bs93(data);
+            cart.bs93(data)?;
         } else if cart.is_nointro(data) {
             cart.nointro(data);
         }
@@ -146,8 +147,7 @@ impl Cartridge {
     }
 
     fn is_bs93(&self, file_content: &[u8]) -> bool {
-        file_content.len() > BS93_HEADER_LENGTH && 
-        &file_content[6..=9] == b"BS93"
+        BS93Header::is_bs93(file_content)
     }
 
     fn is_lnx(&self, file_content: &[u8]) -> bool {
@@ -159,17 +159,19 @@ impl Cartridge {
         check_no_intro(file_content).is_ok()
     }
 
-    fn bs93(&mut self, file_content: &[u8]) {
+    fn bs93(&mut self, file_content: &[u8]) -> Result<(), &'static str> {
+        let header = BS93Header::from_slice(file_content)?;
+        // the loader only copies the length given in the header, it always fits in a 256K cart
+        let program = &file_content[..header.length() as usize];
         let mut cart = CartridgeGeneric::new(1024, &_256K_PINS);
-        let mut content: Vec<u8> = vec![];
+        let mut content: Vec<u8> = Vec::with_capacity(_256K);
         content.extend(BLL_LOADER);
-        content.extend(file_content);
-        let fill_size: usize = 256 * usize::pow(2, 10) - BLL_LOADER.len() - file_content.len();
-        let fill_vec: Vec<u8> = vec![0; fill_size];
-        content.extend(fill_vec);
+        content.extend(program);
+        content.resize(_256K, 0);
         cart.load(&content);
         self.cart = CartType::Generic(cart);
         self.healthy = true;
+        Ok(())
     }
 
     fn nointro(&mut self, file_content: &[u8]) {
@@ -264,10 +266,22 @@ impl Cartridge {
         self.set_cart_pins(pins);
     }
 
+    pub fn is_inserted(&self) -> bool {
+        matches!(self.cart, CartType::Generic(_))
+    }
+
+    /* True when both carts read from the same image in memory, as a cart and its clones do until written to. */
+    pub fn shares_image_with(&self, other: &Cartridge) -> bool {
+        match (&self.cart, &other.cart) {
+            (CartType::Generic(a), CartType::Generic(b)) => a.shares_image_with(b),
+            _ => false,
+        }
+    }
+
     fn cart_pins(&self) -> u32 {
         match &self.cart {
             CartType::Generic(c) => c.pins(),
-            _ => panic!("Trying to write to inexistant cart."),
+            CartType::None() => 0,
         }
     }
 
@@ -279,10 +293,9 @@ impl Cartridge {
     }
 
     fn set_cart_pins(&mut self, pins: u32) {
-        match &mut self.cart {
-            CartType::Generic(c) => c.set_pins(pins),
-            _ => panic!("Trying to write to inexistant cart."),
-        };
+        if let CartType::Generic(c) = &mut self.cart {
+            c.set_pins(pins);
+        }
         if let Some(ee) = &mut self.eeprom {
             ee.tick(pins);
         }
@@ -300,10 +313,43 @@ impl Cartridge {
         self.set_cart_pins(pins);
     }
 
+    /* CPU read of RCART0 at the address latched in Mikey, done at once. Used when the boot is emulated. */
+    pub fn peek0(&mut self, mikey_regs: &mut MikeyRegisters) -> u8 {
+        self.write_address_to_pins(mikey_regs.cart_shift(), mikey_regs.cart_position(), mikey_regs.audin());
+        let data = self.strobe0(mikey_regs);
+        self.clock_ripple(mikey_regs);
+        data
+    }
+
+    fn strobe0(&mut self, mikey_regs: &MikeyRegisters) -> u8 {
+        match self.is_inserted() && mikey_regs.data(SYSCTL1) & SYSCTL1_POWER != 0 {
+            false => 0xff,
+            true => {
+                self.set_pin(CART_PIN_CE);
+                let data = read_pins_u8(self.cart_pins(), &DATA_PINS);
+                self.clear_pin(CART_PIN_CE);
+                data
+            }
+        }
+    }
+
+    /* The end of the strobe clocks the ripple counter. Like the shift register, it's supplied by the cart
+    power and doesn't count while it's off. */
+    fn clock_ripple(&mut self, mikey_regs: &mut MikeyRegisters) {
+        if mikey_regs.data(SYSCTL1) & SYSCTL1_POWER == 0 {
+            return;
+        }
+        mikey_regs.inc_cart_position();
+        self.write_address_to_pins(mikey_regs.cart_shift(), mikey_regs.cart_position(), mikey_regs.audin());
+    }
+
     pub fn rotation(&self) -> LNXRotation {
         self.header.rotation()
     }
 
+    /* A CPU access to RCART0 or RCART1 is a single Suzy cycle: the cart strobe lasts for the whole cycle
+    instead of the decoding and handshake of the Suzy registers, and the ripple counter moves on with it,
+    Mikey isn't involved. The sprite engine only reads RAM, the CPU is the only one reaching the cart. */
     pub fn tick(&mut self, bus: &mut Bus, mikey_regs: &mut MikeyRegisters, switches: &mut Switches) {
         let buss = bus.status();
 
@@ -330,33 +376,27 @@ impl Cartridge {
             1 => {
                  match buss {
                     BusStatus::PeekCart0 => { 
-                        if mikey_regs.data(SYSCTL1) & SYSCTL1_POWER != 0 {
-                            self.set_pin(CART_PIN_CE); 
-                            let data = read_pins_u8(self.cart_pins(), &DATA_PINS);
-                            bus.set_data(data);
-                            self.clear_pin(CART_PIN_CE); 
-                        }
-                        else {
-                            bus.set_data(0xff);
-                        }
-                        bus.set_status(BusStatus::PeekIncCartRipple);
+                        let data = self.strobe0(mikey_regs);
+                        bus.set_data(data);
+                        bus.set_status(BusStatus::PeekDone);
                         switches.set(Switches::cart0_inactive, true);
                     },
                     BusStatus::PeekCart1 => { 
                         bus.set_data(0xff);
-                        bus.set_status(BusStatus::PeekIncCartRipple);
+                        bus.set_status(BusStatus::PeekDone);
                         switches.set(Switches::cart1_inactive, true);
                     }
                     BusStatus::PokeCart0 => { 
-                        bus.set_status(BusStatus::PokeIncCartRipple);
+                        bus.set_status(BusStatus::PokeDone);
                         switches.set(Switches::cart0_inactive, true);
                     },
                     BusStatus::PokeCart1 => { 
-                        bus.set_status(BusStatus::PokeIncCartRipple);
+                        bus.set_status(BusStatus::PokeDone);
                         switches.set(Switches::cart1_inactive, true);
                     }
                     _ => ()
                 }
+                self.clock_ripple(mikey_regs);
                 self.ticks_to_done = 0;
             }
             _ => self.ticks_to_done -= 1,
@@ -370,7 +410,170 @@ impl Cartridge {
                 CartType::Generic(to) => to.copy_from(from),
                 _ => panic!("Trying to write to inexistant cart."),
             }
-            _ => panic!("Trying to read from an inexistant cart."),
+            CartType::None() => self.cart = CartType::None(),
           };
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lynx::Lynx;
+
+    fn content(i: usize) -> u8 {
+        (i ^ (i >> 8) ^ (i >> 16)) as u8
+    }
+
+    /* 1 MB cart: 4 KB blocks, AUDIN is the address line above the ripple counter. */
+    fn cart_1m() -> Cartridge {
+        let mut lnx = vec![0; LNX_HEADER_LENGTH];
+        lnx[0..4].copy_from_slice(b"LYNX");
+        lnx[4] = 0x00;
+        lnx[5] = 0x10;
+        lnx.extend((0.._1024K).map(content));
+        Cartridge::from_slice(&lnx).unwrap()
+    }
+
+    fn select_block(cart: &mut Cartridge, regs: &mut MikeyRegisters, block: u8, audin: u16) {
+        for bit in (0..8).rev() {
+            regs.shift_cart_shift((block >> bit) & 1);
+        }
+        regs.set_audin(audin);
+        cart.write_address_to_pins(regs.cart_shift(), regs.cart_position(), regs.audin());
+    }
+
+    #[test]
+    fn audin_bank_switching() {
+        let mut cart = cart_1m();
+        let mut regs = MikeyRegisters::new();
+        regs.set_data(SYSCTL1, SYSCTL1_POWER);
+
+        for (block, audin) in [(0x00, 0), (0x00, 1), (0x35, 1), (0xFF, 0), (0xFF, 1)] {
+            select_block(&mut cart, &mut regs, block, audin);
+            let base = block as usize * 4096 + audin as usize * 2048;
+            for offset in 0..4 {
+                assert_eq!(cart.peek0(&mut regs), content(base + offset));
+            }
+        }
+
+        /* The ripple counter only reaches the lower half of a block, the upper half needs AUDIN. */
+        select_block(&mut cart, &mut regs, 0x12, 0);
+        let data: Vec<u8> = (0..2048 + 2).map(|_| cart.peek0(&mut regs)).collect();
+        assert_eq!(data[2048..], data[..2]);
+        regs.set_audin(1);
+        regs.reset_cart_position();
+        cart.write_address_to_pins(regs.cart_shift(), regs.cart_position(), regs.audin());
+        assert_eq!(cart.peek0(&mut regs), content(0x12 * 4096 + 2048));
+    }
+
+    #[test]
+    fn cart_strobes() {
+        let mut cart = cart_1m();
+        let mut regs = MikeyRegisters::new();
+        let mut bus = Bus::new();
+        let mut switches = Switches::all();
+        regs.set_data(SYSCTL1, SYSCTL1_POWER);
+        select_block(&mut cart, &mut regs, 0x01, 0);
+
+        for (status, done, strobe) in [
+            (BusStatus::PeekCart0, BusStatus::PeekDone, Switches::cart0_inactive),
+            (BusStatus::PeekCart1, BusStatus::PeekDone, Switches::cart1_inactive),
+            (BusStatus::PokeCart0, BusStatus::PokeDone, Switches::cart0_inactive),
+        ] {
+            let position = regs.cart_position();
+            bus.set_status(status);
+            while bus.status() != done {
+                cart.tick(&mut bus, &mut regs, &mut switches);
+                assert_eq!(switches.contains(strobe), bus.status() == done);
+            }
+            /* The strobe lasts for the whole cycle, the counter moves on at its end. */
+            assert_eq!(regs.cart_position(), position + 1);
+            bus.set_status(BusStatus::None);
+        }
+        assert_eq!(bus.data(), 0xff);
+
+        regs.set_data(SYSCTL1, 0);
+        bus.set_status(BusStatus::PeekCart0);
+        while bus.status() != BusStatus::PeekDone {
+            cart.tick(&mut bus, &mut regs, &mut switches);
+        }
+        assert_eq!((bus.data(), regs.cart_position()), (0xff, 3));
+    }
+
+    /* Ticks for the program to run 8 absolute accesses with `opcode` at `addr`. */
+    fn access_ticks(opcode: u8, addr: u16) -> u64 {
+        const MARK: u16 = 0x0300;
+        let mut prg = vec![0x80, 0x08, 0x02, 0x00, 0x00, 0x00, b'B', b'S', b'9', b'3'];
+        for _ in 0..8 {
+            prg.extend_from_slice(&[opcode, addr as u8, (addr >> 8) as u8]);
+        }
+        lda_sta(&mut prg, 1, MARK);
+        prg.extend_from_slice(&[0x80, 0xFE]);
+        let len = prg.len();
+        prg[5] = len as u8;
+
+        let mut lynx = Lynx::new();
+        lynx.load_bs93_direct(&prg).unwrap();
+        while lynx.ram().get(MARK) != 1 {
+            assert!(lynx.ticks() < 10_000);
+            lynx.tick();
+        }
+        lynx.ticks()
+    }
+
+    #[test]
+    fn cpu_cart_cycles() {
+        /* "The CPU cycle that performed the actual read uses 15 ticks of the clock", a normal RAM read
+        takes 5. A cart write is a plain Suzy write, 5 ticks like a RAM write. */
+        let ram_read = access_ticks(0xAD, 0x0400);
+        assert_eq!(access_ticks(0xAD, RCART0), ram_read + 8 * (15 - 5));
+        assert_eq!(access_ticks(0xAD, RCART1), ram_read + 8 * (15 - 5));
+        assert_eq!(access_ticks(0x8D, RCART0), access_ticks(0x8D, 0x0400));
+    }
+
+    fn lda_sta(prg: &mut Vec<u8>, v: u8, addr: u16) {
+        prg.extend_from_slice(&[0xA9, v, 0x8D, addr as u8, (addr >> 8) as u8]);
+    }
+
+    fn lda(prg: &mut Vec<u8>, addr: u16) {
+        prg.extend_from_slice(&[0xAD, addr as u8, (addr >> 8) as u8]);
+    }
+
+    #[test]
+    fn power_toggle() {
+        const MARK: u16 = 0x0300;
+        let mut prg = vec![0x80, 0x08, 0x02, 0x00, 0x00, 0x00, b'B', b'S', b'9', b'3'];
+        lda_sta(&mut prg, IODAT_CAD, IODAT);
+        lda_sta(&mut prg, SYSCTL1_POWER | SYSCTL1_CAS, SYSCTL1);
+        lda_sta(&mut prg, SYSCTL1_POWER, SYSCTL1);
+        lda_sta(&mut prg, SYSCTL1_POWER | SYSCTL1_CAS, SYSCTL1);
+        lda_sta(&mut prg, SYSCTL1_POWER, SYSCTL1);
+        for _ in 0..3 {
+            lda(&mut prg, RCART0);
+        }
+        lda_sta(&mut prg, 1, MARK);
+        /* Power off, the strobes and reads don't reach the counters. */
+        lda_sta(&mut prg, 0, SYSCTL1);
+        lda_sta(&mut prg, SYSCTL1_CAS, SYSCTL1);
+        lda_sta(&mut prg, 0, SYSCTL1);
+        lda(&mut prg, RCART0);
+        lda_sta(&mut prg, 2, MARK);
+        lda_sta(&mut prg, SYSCTL1_POWER | SYSCTL1_CAS, SYSCTL1);
+        lda(&mut prg, RCART0);
+        lda_sta(&mut prg, 3, MARK);
+        prg.extend_from_slice(&[0x80, 0xFE]);
+        let len = prg.len();
+        prg[5] = len as u8;
+
+        let mut lynx = Lynx::new();
+        lynx.load_bs93_direct(&prg).unwrap();
+        for (mark, shift, position) in [(1, 0b11, 3), (2, 0, 0), (3, 0, 1)] {
+            while lynx.ram().get(MARK) != mark {
+                assert!(lynx.ticks() < 100_000, "Program never reached mark {}.", mark);
+                lynx.tick();
+            }
+            let regs = lynx.mikey().registers();
+            assert_eq!((regs.cart_shift(), regs.cart_position()), (shift, position), "mark {}", mark);
+        }
+    }
+}
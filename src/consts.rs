@@ -49,8 +49,14 @@ pub const SUZY_READ_TICKS: u16 = 11; // ~~
 pub const SUZY_DATA_BUFFER_LEN: u16 = 1;
 // "The longest process is 30 ticks. Adding the overhead of accepting the bus request and releasing the bus grant brings the total to 40 ticks."
 pub const SUZY_BUS_GRANT_TICKS: u16 = 10;
-pub const SUZY_SPRITE_SCB_ADDITIONAL_COST: u16 = 75;
-pub const SUZY_SPRITE_VERT_ADDITIONAL_COST: u16 = 60;
+/* The sprite engine goes through the same RAM cycles as the CPU, in ticks rather than in countdowns.
+The RAM is made of two 4 bits wide chips, a pixel is written without reading its byte first.
+Only XOR sprites and the collision buffer need a read-modify-write.
+There is no measured figure for the work of the engine between its RAM accesses. Handy (susie.cpp,
+PaintSprites), and Mednafen which is derived from it, only charge the RAM cycles, so does this emulator. */
+pub const SUZY_RAM_PAGE_READ_TICKS: u16 = RAM_PAGE_READ_TICKS as u16 + 1;
+pub const SUZY_RAM_NORMAL_READ_TICKS: u16 = RAM_NORMAL_READ_TICKS as u16 + 1;
+pub const SUZY_RAM_WRITE_TICKS: u16 = RAM_NORMAL_WRITE_TICKS as u16 + 1;
 /* "
 Multiplies with out sign or accumulate take 44 ticks to complete.
 Multiplies with sign and accumulate take 54 ticks to complete. 
@@ -58,8 +64,9 @@ Multiplies with sign and accumulate take 54 ticks to complete.
 pub const SUZY_MULT_SIGN_TICKS: u16 = 54;
 pub const SUZY_MULT_NON_SIGN_TICKS: u16 = 44;
 
-// "The CPU cycle that performed the actual read uses 15 ticks of the clock."
-pub const CART_READ_TICKS: u8 = 15-1;
+/* "The CPU cycle that performed the actual read uses 15 ticks of the clock."
+The ripple counter is clocked at the end of the strobe, it doesn't add a Mikey cycle. */
+pub const CART_READ_TICKS: u8 = 15;
 // "This is a blind write from the CPU and must not be interrupted by another access to Suzy until it is finished."
 pub const CART_WRITE_TICKS: u8 = SUZY_WRITE_TICKS as u8; 
 
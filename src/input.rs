@@ -0,0 +1,222 @@
+use serde::{Serialize, Deserialize};
+use crate::cartridge::lnx_header::LNXRotation;
+use crate::suzy::registers::{Joystick, Switches};
+
+/* Buttons as seen by the player. Directions are interpreted according to the `InputOrientation`
+they are applied with, A and B are the outer and inner fire buttons. */
+#[derive(Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct LynxInput {
+    pub up: bool,
+    pub down: bool,
+    pub left: bool,
+    pub right: bool,
+    pub a: bool,
+    pub b: bool,
+    pub option_1: bool,
+    pub option_2: bool,
+    pub pause: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub enum InputOrientation {
+    /* Directions relative to the picture as displayed by the frontend, which already accounts for the cart rotation. */
+    #[default]
+    Screen,
+    /* Directions relative to the physical d-pad of an unrotated Lynx. */
+    Hardware,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum InputLatch {
+    Immediate,
+    NextFrame,
+    /* Start of the frame, i.e. line 0 right after vsync. */
+    AtFrame(u64),
+    /* Frame number and line (HSYNC count since vsync). */
+    AtLine(u64, u16),
+    AtTick(u64),
+}
+
+impl LynxInput {
+    pub fn from_joystick_switches(joystick: Joystick, switches: Switches) -> Self {
+        Self {
+            up: joystick.contains(Joystick::up),
+            down: joystick.contains(Joystick::down),
+            left: joystick.contains(Joystick::left),
+            right: joystick.contains(Joystick::right),
+            a: joystick.contains(Joystick::outside),
+            b: joystick.contains(Joystick::inside),
+            option_1: joystick.contains(Joystick::option_1),
+            option_2: joystick.contains(Joystick::option_2),
+            pause: switches.contains(Switches::pause),
+        }
+    }
+
+    /* JOYSTICK register value. With the default right handed setting the hardware reports the
+    directions flipped, rotated carts additionally turn the d-pad a quarter. */
+    pub fn joystick(&self, orientation: InputOrientation, rotation: LNXRotation, left_handed: bool) -> Joystick {
+        let (mut up, mut down, mut left, mut right) = (self.up, self.down, self.left, self.right);
+
+        if orientation == InputOrientation::Screen {
+            (up, down, left, right) = match rotation {
+                LNXRotation::_270 => (right, left, up, down),
+                LNXRotation::_90 => (left, right, up, down),
+                LNXRotation::None => (up, down, left, right),
+            };
+        }
+
+        if !left_handed {
+            (up, down, left, right) = (down, up, right, left);
+        }
+
+        let mut j = Joystick::empty();
+        j.set(Joystick::up, up);
+        j.set(Joystick::down, down);
+        j.set(Joystick::left, left);
+        j.set(Joystick::right, right);
+        j.set(Joystick::outside, self.a);
+        j.set(Joystick::inside, self.b);
+        j.set(Joystick::option_1, self.option_1);
+        j.set(Joystick::option_2, self.option_2);
+        j
+    }
+
+    /* SWITCHES register value, cart strobes are kept from `switches`. */
+    pub fn switches(&self, mut switches: Switches) -> Switches {
+        switches.set(Switches::pause, self.pause);
+        switches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::suzy::registers::joystick_swap;
+
+    fn legacy_mapping(joy: u8, rotation: LNXRotation, left_handed: bool) -> Joystick {
+        let mut j = Joystick::from_bits_truncate(joy);
+        match rotation {
+            LNXRotation::_270 => {
+                j = joystick_swap(j, Joystick::down, Joystick::right);
+                j = joystick_swap(j, Joystick::up, Joystick::left);
+                j = joystick_swap(j, Joystick::up, Joystick::down);
+            }
+            LNXRotation::_90 => {
+                j = joystick_swap(j, Joystick::up, Joystick::left);
+                j = joystick_swap(j, Joystick::down, Joystick::right);
+            }
+            _ => ()
+        }
+        if !left_handed {
+            j = joystick_swap(j, Joystick::up, Joystick::down);
+            j = joystick_swap(j, Joystick::left, Joystick::right);
+        }
+        j
+    }
+
+    #[test]
+    fn screen_mapping_matches_joystick_swaps() {
+        for rotation in [LNXRotation::None, LNXRotation::_270, LNXRotation::_90] {
+            for left_handed in [false, true] {
+                for joy in 0..=255u8 {
+                    let input = LynxInput::from_joystick_switches(Joystick::from_bits_truncate(joy), Switches::empty());
+                    assert_eq!(
+                        input.joystick(InputOrientation::Screen, rotation, left_handed).bits(),
+                        legacy_mapping(joy, rotation, left_handed).bits()
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn hardware_mapping_ignores_rotation() {
+        let input = LynxInput { up: true, a: true, ..Default::default() };
+        for rotation in [LNXRotation::None, LNXRotation::_270, LNXRotation::_90] {
+            assert_eq!(input.joystick(InputOrientation::Hardware, rotation, true), Joystick::up | Joystick::outside);
+            assert_eq!(input.joystick(InputOrientation::Hardware, rotation, false), Joystick::down | Joystick::outside);
+        }
+    }
+
+    #[test]
+    fn pause_keeps_cart_switches() {
+        let input = LynxInput { pause: true, ..Default::default() };
+        assert_eq!(input.switches(Switches::cart0_inactive), Switches::cart0_inactive | Switches::pause);
+        assert_eq!(LynxInput::default().switches(Switches::pause), Switches::empty());
+    }
+
+    fn test_lynx() -> crate::lynx::Lynx {
+        // BS93 executable spinning on `JMP $0200`
+        const SPIN: [u8; 13] = [0x80, 0x08, 0x02, 0x00, 0x00, 0x0D, b'B', b'S', b'9', b'3', 0x4C, 0x00, 0x02];
+        let mut lynx = crate::lynx::Lynx::new();
+        lynx.load_cart_from_slice(&SPIN).unwrap();
+        lynx
+    }
+
+    fn state<T: Serialize>(t: &T) -> alloc::vec::Vec<u8> {
+        let mut buf = vec![0; 0x20000];
+        let len = postcard::to_slice(t, &mut buf).unwrap().len();
+        buf.truncate(len);
+        buf
+    }
+
+    #[test]
+    fn input_latched_at_tick() {
+        let mut lynx = test_lynx();
+        let input = LynxInput { b: true, pause: true, ..Default::default() };
+        lynx.set_input(input, InputOrientation::Hardware, InputLatch::AtTick(100));
+        while lynx.ticks() < 100 {
+            assert_eq!(lynx.pending_inputs(), 1);
+            assert!(!lynx.joystick().contains(Joystick::inside));
+            lynx.tick();
+        }
+        lynx.tick();
+        assert_eq!(lynx.pending_inputs(), 0);
+        assert!(lynx.joystick().contains(Joystick::inside));
+        assert!(lynx.switches().contains(Switches::pause));
+    }
+
+    #[test]
+    fn input_queue_independent_of_tick_chunking() {
+        let inputs = [
+            (LynxInput { up: true, ..Default::default() }, InputLatch::AtLine(2, 20)),
+            (LynxInput { a: true, ..Default::default() }, InputLatch::AtFrame(3)),
+            (LynxInput { option_1: true, ..Default::default() }, InputLatch::AtLine(3, 50)),
+        ];
+
+        // host queueing everything upfront and running tick by tick
+        let mut lynx_a = test_lynx();
+        for (input, latch) in inputs {
+            lynx_a.set_input(input, InputOrientation::Screen, latch);
+        }
+
+        // host queueing each input a frame ahead and running instruction by instruction
+        let mut lynx_b = test_lynx();
+        let mut queued = inputs.iter().peekable();
+        while lynx_b.mikey().video().frame_count() < 4 {
+            while let Some(&&(input, latch)) = queued.peek() {
+                let frame = match latch {
+                    InputLatch::AtFrame(f) | InputLatch::AtLine(f, _) => f,
+                    _ => unreachable!(),
+                };
+                if lynx_b.mikey().video().frame_count() + 1 < frame {
+                    break;
+                }
+                lynx_b.set_input(input, InputOrientation::Screen, latch);
+                queued.next();
+            }
+            lynx_b.step_instruction();
+        }
+
+        while lynx_a.ticks() < lynx_b.ticks() {
+            lynx_a.tick();
+        }
+        assert_eq!(lynx_a.pending_inputs(), 0);
+        assert_eq!(lynx_b.pending_inputs(), 0);
+        assert_eq!(lynx_a.joystick(), Joystick::option_1);
+
+        assert!(state(lynx_a.ram()) == state(lynx_b.ram()));
+        assert!(state(lynx_a.suzy()) == state(lynx_b.suzy()));
+        assert!(state(lynx_a.mikey()) == state(lynx_b.mikey()));
+    }
+}
@@ -2,16 +2,23 @@
 
 #[macro_use]
 extern crate alloc;
+#[cfg(any(feature = "comlynx_socket", feature = "comlynx_shared_memory"))]
+extern crate std;
 
+pub mod boot;
 pub mod bus;
 pub mod cartridge;
+pub mod input;
 pub mod mikey;
 pub mod ram;
 pub mod rom;
+pub mod run_ahead;
 pub mod suzy;
 pub mod vectors;
 pub mod consts;
 pub mod lynx;
+#[cfg(not(feature = "comlynx_shared_memory"))]
+pub mod netplay;
 mod shared_memory;
 
 pub fn serialize(lynx: &lynx::Lynx, data: &mut [u8]) -> Result<(), &'static str> {
@@ -21,12 +28,17 @@ pub fn serialize(lynx: &lynx::Lynx, data: &mut [u8]) -> Result<(), &'static str>
     }
 }
 
+/* The loaded console is plugged on the ComLynx cable of `source`, the level of the wire is the live one. */
 pub fn deserialize(data: &[u8], source: &lynx::Lynx) -> Result<lynx::Lynx, &'static str> {
     let mut lynx = match postcard::from_bytes::<lynx::Lynx>(data) {
         Err(_) => return Err("Deserialization error"),
         Ok(l) => l
     };
+    lynx.set_comlynx_cable(source.comlynx_cable());
     lynx.cart_mut().copy_from(source.cart());
+    lynx.set_color_profile(source.color_profile());
+    lynx.set_ghosting(source.ghosting());
+    lynx.set_fast_sprites(source.fast_sprites());
     Ok(lynx)
 }
 
@@ -1,23 +1,28 @@
 
-use alloc::vec::Vec;
+use alloc::{boxed::Box, collections::VecDeque, vec::Vec};
+use crate::boot::{decrypt_frame, encrypted_frame_len, BootMode, BOOT_MIKEY_REGISTERS, HLE_LOAD_ADDRESS};
 use crate::bus::*;
 use crate::cartridge::*;
 use crate::consts::*;
-use crate::cartridge::lnx_header::LNXRotation;
-use log::trace;
+use crate::cartridge::{bs93_header::{BS93Header, BS93_HEADER_LENGTH}, lnx_header::LNXRotation};
+use crate::input::{InputLatch, InputOrientation, LynxInput};
+use log::{error, trace};
 #[cfg(not(feature = "comlynx_shared_memory"))]
 use crate::mikey::uart::comlynx_cable_mutex::ComlynxCable;
 #[cfg(feature = "comlynx_shared_memory")]
 use crate::mikey::uart::comlynx_cable_shared_memory::ComlynxCable;
-use crate::mikey::{video::{LYNX_SCREEN_HEIGHT, LYNX_SCREEN_WIDTH}, Mikey};
+use crate::mikey::uart::{comlynx_capture::ComlynxCapture, comlynx_fault::ComlynxFault, comlynx_transport::ComlynxTransport, comlynx_uploader::ComlynxUploader};
+use crate::mikey::{video::{ColorProfile, VideoTiming, LYNX_SCREEN_HEIGHT, LYNX_SCREEN_WIDTH}, Mikey};
 use crate::ram::*;
-use crate::rom::Rom;
+use crate::rom::{Rom, RomInfo};
 use crate::shared_memory::SharedMemory;
-use crate::suzy::{registers::{joystick_swap, Joystick, Switches}, Suzy};
+use crate::suzy::{registers::{Joystick, Switches}, unsafe_access::UnsafeAccessHook, Suzy};
 use crate::vectors::Vectors;
 use serde::{Serialize, Deserialize};
 
-#[derive(Serialize, Deserialize)]
+/* A clone runs on its own: its ComLynx cable is detached at the current level and it has no transport,
+capture or fault. The cart image is shared with the original. */
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Lynx {
     ram: Ram,
     rom: Rom,
@@ -29,6 +34,9 @@ pub struct Lynx {
     bus: Bus,
     last_ir_pc: u16,
     switches_cache: Switches,
+    input_queue: VecDeque<(LynxInput, InputOrientation, InputLatch)>,
+    boot_mode: BootMode,
+    ram_init: RamInit,
 }
 
 impl Lynx {
@@ -44,16 +52,95 @@ impl Lynx {
             bus: Bus::default(),
             last_ir_pc: 0,
             switches_cache: Switches::empty(),
+            input_queue: VecDeque::new(),
+            boot_mode: BootMode::default(),
+            ram_init: RamInit::default(),
         };
         slf.initialize();        
         slf
     }
 
     fn initialize(&mut self) {
+        /* The free boot ROM can't run retail carts loaders, they need the block selection routine of the official one. */
+        let hle = self.boot_mode == BootMode::Hle && self.cart.is_inserted();
+        if hle && self.rom.is_free_bootrom() {
+            self.rom = Rom::hle();
+        } else if !hle && self.rom.is_hle() {
+            self.rom = Rom::default();
+        }
+        self.map_rom();
+        if hle {
+            match self.hle_boot() {
+                Ok(()) => return,
+                Err(e) => {
+                    /* hle_boot may have cleared the RAM, set up Mikey and moved the cart counters already. */
+                    error!("HLE boot failed, running the boot ROM: {}", e);
+                    if self.rom.is_hle() {
+                        self.rom = Rom::default();
+                    }
+                    self.ram.power_on(self.ram_init);
+                    self.mikey.reset();
+                    self.cart.reset();
+                    self.map_rom();
+                }
+            }
+        }
+        let reset_vec = self.vectors.reset();
+        self.mikey.cpu_prefetch(reset_vec, self.rom.get(reset_vec));
+    }
+
+    fn map_rom(&mut self) {
+        if let Err(e) = self.rom.check_reset_vector() {
+            error!("{} Using the free boot ROM instead.", e);
+            self.rom = Rom::default();
+        }
         self.vectors.from_slice(&self.rom.as_slice()[0x1FA..]);
         self.ram.set_mmapctl(self.rom.as_slice()[0x1F9]);
-        let reset_vec = self.vectors.reset();
-        self.mikey.cpu_prefetch(reset_vec, &mut self.rom);
+    }
+
+    /* Does what the boot ROM does: Mikey is set up, the encrypted loader at the start of the cart is
+    decrypted to $0200 and started, with the cart counters left right after it. Like the ROM, the RAM is
+    cleared and $05-$06 point after the loaded data. */
+    fn hle_boot(&mut self) -> Result<(), &'static str> {
+        let mmapctl = self.ram.mmapctl();
+        self.ram.fill(0);
+        self.ram.set_mmapctl(mmapctl);
+        for (addr, data) in BOOT_MIKEY_REGISTERS {
+            self.mikey.write(addr, data, &mut self.cart);
+        }
+
+        let first = self.cart.peek0(self.mikey.registers_mut());
+        let mut frame = vec![first];
+        for _ in 1..encrypted_frame_len(first) {
+            frame.push(self.cart.peek0(self.mikey.registers_mut()));
+        }
+        let loader = decrypt_frame(&frame)?;
+        trace!("HLE boot, loader of {} bytes", loader.len());
+
+        self.ram.copy(HLE_LOAD_ADDRESS, &loader);
+        let end = HLE_LOAD_ADDRESS + loader.len() as u16;
+        self.ram.set(0x0005, end as u8);
+        self.ram.set(0x0006, (end >> 8) as u8);
+        self.mikey.cpu_prefetch(HLE_LOAD_ADDRESS, self.ram.get(HLE_LOAD_ADDRESS));
+        Ok(())
+    }
+
+    pub fn boot_mode(&self) -> BootMode {
+        self.boot_mode
+    }
+
+    /* Takes effect at the next `reset()` or cart load. */
+    pub fn set_boot_mode(&mut self, boot_mode: BootMode) {
+        self.boot_mode = boot_mode;
+    }
+
+    pub fn ram_init(&self) -> RamInit {
+        self.ram_init
+    }
+
+    /* Takes effect at the next `reset()`. */
+    pub fn set_ram_init(&mut self, ram_init: RamInit) {
+        self.ram_init = ram_init;
     }
 
     pub fn load_cart_from_slice(&mut self, data: &[u8]) -> Result<(), &'static str> {
@@ -62,11 +149,34 @@ impl Lynx {
             Err(e) => Err(e),
             Ok(c) => {
                 self.cart = c;
+                if self.boot_mode == BootMode::Hle {
+                    self.reset();
+                }
                 Ok(())
             }
         }
     }
 
+    /* Runs a BS93 `.o` file without the boot ROM and the BLL loader. The console is reset, the program is
+    copied into RAM at its load address without its header, like the BLL loader does, and the CPU starts
+    there. MAPCTL is cleared, Mikey, Suzy, the ROM and the vectors are visible, the program is expected to set
+    up the display itself like the BLL and cc65 startup code do. The cart slot is left empty. */
+    pub fn load_bs93_direct(&mut self, data: &[u8]) -> Result<(), &'static str> {
+        let header = BS93Header::from_slice(data)?;
+        let program = &data[BS93_HEADER_LENGTH..header.length() as usize];
+        if header.load_address() as usize + program.len() > MMC_ADDR as usize {
+            return Err("BS93 program doesn't fit in RAM.");
+        }
+        trace!("Direct load of {} bytes at 0x{:04x}", program.len(), header.load_address());
+
+        self.cart = Cartridge::default();
+        self.reset();
+        self.ram.copy(header.load_address(), program);
+        self.ram.set_mmapctl(0);
+        self.mikey.cpu_prefetch(header.load_address(), self.ram.get(header.load_address()));
+        Ok(())
+    }
+
     pub fn load_rom_from_slice(&mut self, data: &[u8]) -> Result<(), &'static str> {
         trace!("Load rom");
         match Rom::from_slice(data) {
@@ -143,6 +253,10 @@ impl Lynx {
     }
 
     pub fn tick(&mut self) {
+        if !self.input_queue.is_empty() {
+            self.latch_queued_inputs();
+        }
+
         match self.bus.status() {
             BusStatus::PokeCore => self.poke(),
             BusStatus::PeekCore => self.peek(),
@@ -188,6 +302,10 @@ impl Lynx {
     pub fn rom(&self) -> &Rom {
         &self.rom
     }
+
+    pub fn rom_info(&self) -> RomInfo {
+        self.rom.info()
+    }
     
     pub fn suzy(&self) -> &Suzy {
         &self.suzy
@@ -215,28 +333,54 @@ impl Lynx {
 
     pub fn set_joystick_u8(&mut self, joy: u8) {
         trace!("Joystick: {:08b}", joy);
+        let input = LynxInput::from_joystick_switches(Joystick::from_bits_truncate(joy), Switches::empty());
+        let j = input.joystick(InputOrientation::Screen, self.rotation(), self.left_handed());
+        self.suzy.set_joystick(j.bits());
+    }
 
-        let mut j = Joystick::from_bits(joy).unwrap();
+    /* Queues the input, it is latched into the JOYSTICK and SWITCHES registers when `latch` is reached.
+    Inputs are latched in the order they were queued, so they must be queued chronologically.
+    As latching only depends on the emulated time, the same input stream always gives the same
+    state whatever the host's `tick()` chunking is. */
+    pub fn set_input(&mut self, input: LynxInput, orientation: InputOrientation, latch: InputLatch) {
+        let latch = match latch {
+            InputLatch::NextFrame => InputLatch::AtFrame(self.mikey.video().frame_count() + 1),
+            l => l,
+        };
+        self.input_queue.push_back((input, orientation, latch));
+        self.latch_queued_inputs();
+    }
 
-        match self.rotation() {
-            LNXRotation::_270 => {
-                j = joystick_swap(j, Joystick::down, Joystick::right);
-                j = joystick_swap(j, Joystick::up, Joystick::left);
-                j = joystick_swap(j, Joystick::up, Joystick::down);
-            }
-            LNXRotation::_90 => {
-                j = joystick_swap(j, Joystick::up, Joystick::left);
-                j = joystick_swap(j, Joystick::down, Joystick::right);
+    fn input_latch_reached(&self, latch: InputLatch) -> bool {
+        let video = self.mikey.video();
+        match latch {
+            InputLatch::Immediate | InputLatch::NextFrame => true,
+            InputLatch::AtFrame(frame) => video.frame_count() >= frame,
+            InputLatch::AtLine(frame, line) => video.frame_count() > frame || (video.frame_count() == frame && video.line() >= line),
+            InputLatch::AtTick(tick) => self.ticks >= tick,
+        }
+    }
+
+    fn latch_queued_inputs(&mut self) {
+        while let Some(&(input, orientation, latch)) = self.input_queue.front() {
+            if !self.input_latch_reached(latch) {
+                return;
             }
-            _ => ()
+            self.input_queue.pop_front();
+            trace!("Input latched: {:?}", input);
+            let j = input.joystick(orientation, self.rotation(), self.left_handed());
+            self.suzy.set_joystick(j.bits());
+            self.switches_cache = input.switches(self.switches_cache);
+            self.suzy.set_switches(self.switches_cache.bits());
         }
-        
-        if !self.left_handed() {
-            j = joystick_swap(j, Joystick::up, Joystick::down);
-            j = joystick_swap(j, Joystick::left, Joystick::right);
-        } 
+    }
 
-        self.suzy.set_joystick(j.bits());
+    pub fn pending_inputs(&self) -> usize {
+        self.input_queue.len()
+    }
+
+    pub fn clear_pending_inputs(&mut self) {
+        self.input_queue.clear();
     }
 
     pub fn set_switches_u8(&mut self, sw: u8) {
@@ -265,6 +409,37 @@ impl Lynx {
         self.mikey.video().rgb_screen()
     }
 
+    pub fn color_profile(&self) -> ColorProfile {
+        self.mikey.registers().color_profile()
+    }
+
+    pub fn set_color_profile(&mut self, profile: ColorProfile) {
+        self.mikey.registers_mut().set_color_profile(profile);
+    }
+
+    pub fn ghosting(&self) -> u8 {
+        self.mikey.video().ghosting()
+    }
+
+    pub fn set_ghosting(&mut self, ghosting: u8) {
+        self.mikey.video_mut().set_ghosting(ghosting);
+    }
+
+    /* Sprites are rendered a pixel line at a time, with the same result and timing, video DMA included,
+    unless the CPU writes to Suzy in the middle of a line. */
+    pub fn fast_sprites(&self) -> bool {
+        self.suzy.fast_sprites()
+    }
+
+    pub fn set_fast_sprites(&mut self, fast: bool) {
+        self.suzy.set_fast_sprites(fast);
+    }
+
+    /* Called on each CPU write to Suzy racing the sprite engine, see `UnsafeAccess`. */
+    pub fn set_unsafe_access_hook(&mut self, hook: Option<Box<dyn UnsafeAccessHook>>) {
+        self.suzy.set_unsafe_access_hook(hook);
+    }
+
     pub fn rotation(&self) -> LNXRotation {
         self.cart.rotation()
     }
@@ -273,15 +448,38 @@ impl Lynx {
         self.suzy.left_handed()
     }
 
+    /* Power cycle: the state is the one of `new()`, with the RAM filled according to `ram_init()`.
+    The cart, boot ROM and emulator settings stay.
+    The CPU starts at the reset vector with A, X and Y cleared, S at 0xFD and interrupts masked. The Mikey
+    timers are stopped with their backup, count and control registers at 0 and no interrupt pending, every
+    palette and attenuation bit is set and the UART is ready to transmit. Suzy reports revision 1, its math
+    registers are all ones with positive signs and H/VSIZOFF are 0x7F. MAPCTL is the ROM byte at $FFF9. */
     pub fn reset(&mut self) {
+        self.ram.power_on(self.ram_init);
+        self.power_up();
+    }
+
+    /* The power is only cut for a moment: everything starts again as after `reset()` but the DRAM keeps
+    its content. The boot ROM clears it anyway, only programs started without it can tell. */
+    pub fn warm_reset(&mut self) {
+        self.ram.reset();
+        self.power_up();
+    }
+
+    fn power_up(&mut self) {
         self.bus = Bus::new();
-        self.ram = Ram::new();
+        self.rom.reset();
         self.vectors = Vectors::new();
-        self.suzy = Suzy::new();
+        let mut suzy = Suzy::new();
+        suzy.set_fast_sprites(self.suzy.fast_sprites());
+        suzy.take_unsafe_access_hook(&mut self.suzy);
+        self.suzy = suzy;
         self.mikey.reset();
         self.cart.reset();
         self.ticks = 0;
         self.last_ir_pc = 0;
+        self.switches_cache = Switches::empty();
+        self.input_queue.clear();
         self.initialize();
     }
 
@@ -298,11 +496,15 @@ impl Lynx {
     }
 
     pub fn display_refresh_rate(&self) -> f64 {
-        1_000_000. / // to sec.
-        (
-            (self.mikey.timers().peek(TIM0BKUP) + 1) as f64 // us per line
-            * 105. // 105 lines
-        )
+        self.video_timing().frame_rate
+    }
+
+    pub fn video_timing(&self) -> VideoTiming {
+        self.mikey.video_timing()
+    }
+
+    pub fn video_timing_changed(&mut self) -> bool {
+        self.mikey.video_timing_changed()
     }
 
     pub fn ram_size(&self) -> usize {
@@ -313,11 +515,33 @@ impl Lynx {
         self.ram.data()
     }
 
-    #[cfg(not(feature = "comlynx_shared_memory"))]
     pub fn set_comlynx_cable(&mut self, cable: &ComlynxCable) {
         self.mikey.set_comlynx_cable(cable);
     }
 
+    pub fn set_comlynx_transport(&mut self, transport: Option<Box<dyn ComlynxTransport>>) {
+        self.mikey.set_comlynx_transport(transport);
+    }
+
+    pub fn set_comlynx_capture(&mut self, capture: Option<&ComlynxCapture>, sender: u8) {
+        self.mikey.set_comlynx_capture(capture, sender);
+    }
+
+    pub fn set_comlynx_fault(&mut self, fault: Option<Box<dyn ComlynxFault>>) {
+        self.mikey.set_comlynx_fault(fault);
+    }
+
+    /* Sends a BS93 `.o` file to the BLL serial loader of the running program, replacing the current transport. */
+    pub fn comlynx_upload(&mut self, file_content: &[u8]) -> Result<(), &'static str> {
+        let uploader = ComlynxUploader::new(file_content)?;
+        self.set_comlynx_transport(Some(Box::new(uploader)));
+        Ok(())
+    }
+
+    pub fn comlynx_baud_rate(&self) -> u32 {
+        self.mikey.comlynx_baud_rate()
+    }
+
     pub fn comlynx_cable(&self) -> &ComlynxCable {
         self.mikey.comlynx_cable()
     }
@@ -325,6 +549,39 @@ impl Lynx {
     pub fn cart_mut(&mut self) -> &mut Cartridge {
         &mut self.cart
     }
+
+    /* In memory copy of the whole state, without serialization. The ComLynx links are not part of it. */
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot { lynx: self.clone() }
+    }
+
+    /* Goes back to `snapshot`. This Lynx keeps its ComLynx links (cable, transport, capture and fault), its
+    color profile, ghosting, sprite rendering mode and unsafe access hook, everything else comes from the snapshot. */
+    pub fn restore(&mut self, snapshot: &Snapshot) {
+        let mut lynx = snapshot.lynx.clone();
+        lynx.mikey.take_comlynx_links(&mut self.mikey);
+        lynx.set_color_profile(self.color_profile());
+        lynx.set_ghosting(self.ghosting());
+        lynx.set_fast_sprites(self.fast_sprites());
+        lynx.suzy.take_unsafe_access_hook(&mut self.suzy);
+        *self = lynx;
+    }
+}
+
+#[derive(Clone)]
+pub struct Snapshot {
+    lynx: Lynx,
+}
+
+impl Snapshot {
+    pub fn ticks(&self) -> u64 {
+        self.lynx.ticks()
+    }
+
+    /* A new detached Lynx starting from this snapshot, to run ahead or search without touching the original. */
+    pub fn to_lynx(&self) -> Lynx {
+        self.lynx.clone()
+    }
 }
 
 impl Default for Lynx {
@@ -332,3 +589,283 @@ impl Default for Lynx {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mikey::{cpu::M6502Flags, registers::SerCtlR};
+    use crate::rom::RomKind;
+
+    // BS93 executable loaded at $1000, storing $42 at $0300 then spinning on `JMP $1005`
+    const STORE: [u8; 18] = [
+        0x80, 0x08, 0x10, 0x00, 0x00, 0x12, b'B', b'S', b'9', b'3',
+        0xA9, 0x42, 0x8D, 0x00, 0x03, 0x4C, 0x05, 0x10,
+    ];
+
+    #[test]
+    fn bs93_direct_load() {
+        let mut lynx = Lynx::new();
+        lynx.load_bs93_direct(&STORE).unwrap();
+        assert!(!lynx.cart().is_inserted());
+        assert_eq!(lynx.ram().get(0x1000), 0xA9);
+        assert_eq!(lynx.ram().mmapctl(), 0);
+        for _ in 0..1000 {
+            lynx.tick();
+        }
+        assert_eq!(lynx.ram().get(0x0300), 0x42);
+        assert_eq!(lynx.mikey().cpu().last_ir_pc, 0x1005);
+    }
+
+    #[test]
+    fn hle_boot() {
+        let mut lynx = Lynx::new();
+        lynx.set_boot_mode(BootMode::Hle);
+        lynx.load_cart_from_slice(&STORE).unwrap();
+        // the decrypted BLL loader is ready to run, the ROM is skipped
+        assert_eq!(lynx.ram().get(0x0200), 0x9A);
+        assert_eq!(lynx.mikey().cpu().pc(), 0x0200);
+        assert_eq!(lynx.ram().get(0x0005), 0x32);
+        assert_eq!(lynx.mikey().registers().data(SYSCTL1) & SYSCTL1_POWER, SYSCTL1_POWER);
+
+        // the loader reads the rest of the cart from where the boot stopped, the program is copied
+        // at its load address without its header
+        while lynx.mikey().cpu().last_ir_pc != 0x1005 {
+            lynx.tick();
+            assert!(lynx.ticks() < 1_000_000);
+        }
+        assert_eq!(lynx.ram().get(0x1000), 0xA9);
+        assert_eq!(lynx.ram().get(0x0300), 0x42);
+        for _ in 0..1000 {
+            lynx.tick();
+        }
+        assert_eq!(lynx.mikey().cpu().last_ir_pc, 0x1005);
+    }
+
+    #[test]
+    fn hle_boot_bad_loader() {
+        // 128K LNX cart full of 0xFF, its first block is not a valid encrypted loader
+        let mut lnx = vec![0; 64];
+        lnx[..4].copy_from_slice(b"LYNX");
+        lnx[5] = 0x02;
+        lnx.resize(64 + 0x20000, 0xFF);
+
+        let mut lynx = Lynx::new();
+        lynx.set_boot_mode(BootMode::Hle);
+        lynx.load_cart_from_slice(&lnx).unwrap();
+        let mut rom = Lynx::new();
+        rom.load_cart_from_slice(&lnx).unwrap();
+
+        // the free boot ROM runs from a clean power on, as if the cart had been loaded in ROM boot mode
+        assert_eq!(lynx.rom_info().kind(), RomKind::Free);
+        assert_eq!(lynx.mikey().cpu().pc(), rom.mikey().cpu().pc());
+        assert_eq!(lynx.ram().get(0x0200), 0xFF);
+        rom.set_boot_mode(BootMode::Hle);
+        assert!(state(&lynx) == state(&rom));
+    }
+
+    #[test]
+    fn rom_info() {
+        let mut lynx = Lynx::new();
+        assert_eq!(lynx.rom_info().kind(), RomKind::Free);
+        lynx.set_boot_mode(BootMode::Hle);
+        lynx.load_cart_from_slice(&STORE).unwrap();
+        assert_eq!(lynx.rom_info().kind(), RomKind::Hle);
+        assert!(lynx.load_rom_from_slice(&[0; 512]).is_err());
+    }
+
+    fn state(lynx: &Lynx) -> Vec<u8> {
+        let mut data = vec![0; 0x40000];
+        crate::serialize(lynx, &mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn snapshot_restore() {
+        let mut lynx = Lynx::new();
+        lynx.load_cart_from_slice(&STORE).unwrap();
+        for _ in 0..100_000 {
+            lynx.tick();
+        }
+        let snapshot = lynx.snapshot();
+        assert_eq!(snapshot.ticks(), lynx.ticks());
+        for _ in 0..100_000 {
+            lynx.tick();
+        }
+        let expected = state(&lynx);
+
+        lynx.restore(&snapshot);
+        assert_eq!(lynx.ticks(), snapshot.ticks());
+        for _ in 0..100_000 {
+            lynx.tick();
+        }
+        assert_eq!(state(&lynx), expected);
+
+        let mut fork = snapshot.to_lynx();
+        assert!(fork.cart().shares_image_with(lynx.cart()));
+        for _ in 0..100_000 {
+            fork.tick();
+        }
+        assert_eq!(state(&fork), expected);
+    }
+
+    #[cfg(not(feature = "comlynx_shared_memory"))]
+    #[test]
+    fn snapshot_cable() {
+        use crate::mikey::uart::redeye_status::RedeyeStatus;
+
+        let mut line = ComlynxCable::default();
+        let mut lynx = Lynx::new();
+        lynx.set_comlynx_cable(&line);
+        let snapshot = lynx.snapshot();
+
+        // forks are detached
+        let fork = snapshot.to_lynx();
+        line.set(RedeyeStatus::Low);
+        assert!(fork.comlynx_cable().status() == RedeyeStatus::High);
+        assert!(lynx.comlynx_cable().status() == RedeyeStatus::Low);
+
+        // restoring keeps the cable plugged
+        lynx.restore(&snapshot);
+        line.set(RedeyeStatus::High);
+        assert!(lynx.comlynx_cable().status() == RedeyeStatus::High);
+        line.set(RedeyeStatus::Low);
+        assert!(lynx.comlynx_cable().status() == RedeyeStatus::Low);
+    }
+
+    #[test]
+    fn reset_keeps_cable() {
+        use crate::mikey::uart::redeye_status::RedeyeStatus;
+
+        #[cfg(not(feature = "comlynx_shared_memory"))]
+        let mut line = ComlynxCable::default();
+        #[cfg(feature = "comlynx_shared_memory")]
+        let mut line = ComlynxCable::with_session("test-reset", 0).unwrap();
+        let mut lynx = Lynx::new();
+        lynx.set_comlynx_cable(&line);
+        lynx.reset();
+        line.set(RedeyeStatus::Low);
+        assert!(lynx.comlynx_cable().status() == RedeyeStatus::Low);
+        line.set(RedeyeStatus::High);
+        assert!(lynx.comlynx_cable().status() == RedeyeStatus::High);
+    }
+
+    #[test]
+    fn deserialize_keeps_cable() {
+        use crate::mikey::uart::redeye_status::RedeyeStatus;
+
+        #[cfg(not(feature = "comlynx_shared_memory"))]
+        let mut line = ComlynxCable::default();
+        #[cfg(feature = "comlynx_shared_memory")]
+        let mut line = ComlynxCable::with_session("test-deserialize", 0).unwrap();
+        let mut lynx = Lynx::new();
+        lynx.set_comlynx_cable(&line);
+        let mut data = vec![0; lynx.serialize_size()];
+        crate::serialize(&lynx, &mut data).unwrap();
+        let loaded = crate::deserialize(&data, &lynx).unwrap();
+        line.set(RedeyeStatus::Low);
+        assert!(loaded.comlynx_cable().status() == RedeyeStatus::Low);
+        line.set(RedeyeStatus::High);
+        assert!(loaded.comlynx_cable().status() == RedeyeStatus::High);
+    }
+
+    #[test]
+    fn bs93_direct_load_errors() {
+        let mut lynx = Lynx::new();
+        assert!(lynx.load_bs93_direct(&STORE[..12]).is_err());
+
+        let mut oversize = STORE;
+        oversize[2] = 0xFF;
+        oversize[3] = 0xF5;
+        assert!(lynx.load_bs93_direct(&oversize).is_err());
+        assert!(Cartridge::from_slice(&STORE[..12]).is_err());
+    }
+
+    fn booted() -> Lynx {
+        let mut lynx = Lynx::new();
+        lynx.load_cart_from_slice(&STORE).unwrap();
+        lynx
+    }
+
+    #[test]
+    fn reset_matches_new() {
+        let mut lynx = booted();
+        for _ in 0..200_000 {
+            lynx.tick();
+        }
+        lynx.reset();
+        let (a, b) = (state(&lynx), state(&booted()));
+        assert!(a == b, "first difference at {:?} of {}", a.iter().zip(&b).position(|(x, y)| x != y), a.len());
+    }
+
+    #[test]
+    fn warm_reset() {
+        let mut lynx = Lynx::new();
+        lynx.load_bs93_direct(&STORE).unwrap();
+        for _ in 0..1000 {
+            lynx.tick();
+        }
+        assert_eq!(lynx.ram().get(0x0300), 0x42);
+
+        let mut cold = lynx.clone();
+        cold.reset();
+        lynx.warm_reset();
+        assert_eq!(cold.ram().get(0x0300), 0xFF);
+        assert_eq!(lynx.ram().get(0x0300), 0x42);
+        assert_eq!(lynx.ticks(), 0);
+        assert_eq!(lynx.mikey().cpu().pc(), cold.mikey().cpu().pc());
+        assert_eq!(lynx.ram().mmapctl(), cold.ram().mmapctl());
+    }
+
+    #[test]
+    fn power_on_state() {
+        let mut lynx = booted();
+        for _ in 0..200_000 {
+            lynx.tick();
+        }
+        lynx.reset();
+
+        let cpu = lynx.mikey().cpu();
+        assert_eq!((cpu.a(), cpu.x(), cpu.y(), cpu.s()), (0, 0, 0, 0xFD));
+        assert!(cpu.flags().contains(M6502Flags::I));
+        assert_eq!(cpu.pc(), lynx.rom_info().reset_vector());
+        assert_eq!(lynx.ram().mmapctl(), lynx.rom.get(MMC_ADDR));
+
+        let timers = lynx.mikey().timers();
+        assert!((TIM0BKUP..=TIM7CTLB).all(|a| timers.peek(a) == 0));
+        assert!((AUD0VOL..=AUD3MISC).all(|a| timers.peek(a) == 0));
+
+        let mikey = lynx.mikey().registers();
+        assert_eq!(mikey.data(INTSET), 0);
+        assert!((GREEN0..=GREENF).all(|a| mikey.data(a) == 0x0F));
+        assert!((BLUERED0..=BLUEREDF).all(|a| mikey.data(a) == 0xFF));
+        assert!((ATTEN_A..=ATTEN_D).all(|a| mikey.data(a) == 0xFF));
+        assert!(mikey.serctl_r_is_flag_set(SerCtlR::tx_rdy | SerCtlR::tx_empty));
+
+        let suzy = lynx.suzy().registers();
+        assert_eq!(suzy.data(SUZYHREV), 1);
+        assert!((MATHD..=MATHN).chain(MATHH..=MATHE).chain(MATHM..=MATHJ).all(|a| suzy.data(a) == 0xFF));
+        assert_eq!((suzy.data(HSIZOFFL), suzy.data(VSIZOFFL)), (0x7F, 0x7F));
+    }
+
+    #[test]
+    fn ram_init() {
+        fn ram(init: RamInit) -> Vec<u8> {
+            let mut lynx = Lynx::new();
+            lynx.set_ram_init(init);
+            lynx.reset();
+            assert_eq!(lynx.ram_init(), init);
+            (0..0xFF00).map(|a| lynx.ram().get(a)).collect()
+        }
+
+        assert!(ram(RamInit::Zero).iter().all(|&v| v == 0));
+        assert!(ram(RamInit::Ones).iter().all(|&v| v == 0xFF));
+        assert_eq!(ram(RamInit::Ones), (0..0xFF00).map(|a| Lynx::new().ram().get(a)).collect::<Vec<u8>>());
+        let stripes = ram(RamInit::Stripes);
+        assert_eq!((stripes[0x0000], stripes[0x0080], stripes[0x0100], stripes[0x01FF]), (0x00, 0xFF, 0x00, 0xFF));
+
+        let random = ram(RamInit::Random(1));
+        assert_eq!(random, ram(RamInit::Random(1)));
+        assert_ne!(random, ram(RamInit::Random(2)));
+        assert!(random.iter().filter(|&&v| v == random[0]).count() < 0x400);
+    }
+}
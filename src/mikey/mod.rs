@@ -5,13 +5,13 @@ pub mod uart;
 pub mod video;
 
 use crate::*;
+use alloc::boxed::Box;
 use bus::{Bus, BusStatus};
 use cartridge::Cartridge;
 use consts::*;
 use cpu::*;
 use log::trace;
 use ram::Ram;
-use rom::Rom;
 use serde::{Deserialize, Serialize};
 use timers::*;
 use registers::*;
@@ -19,6 +19,7 @@ use registers::*;
 use uart::{comlynx_cable_mutex::ComlynxCable, Uart};
 #[cfg(feature = "comlynx_shared_memory")]
 use uart::{comlynx_cable_shared_memory::ComlynxCable, Uart};
+use uart::{comlynx_capture::ComlynxCapture, comlynx_fault::ComlynxFault, comlynx_transport::ComlynxTransport};
 use video::*;
 
 #[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
@@ -34,8 +35,6 @@ pub enum MikeyInstruction {
     PeekIodat,
     PokeIodat,
     PokeOk,
-    PeekIncCartRipple,
-    PokeIncCartRipple,
     CpuSleep,
     PeekDispCtl,
     PokeDispCtl,
@@ -53,7 +52,7 @@ pub enum MikeyBusOwner {
     Cpu,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Mikey {
     cpu: M6502,
     #[serde(skip)]
@@ -69,13 +68,14 @@ pub struct Mikey {
     disp_addr: u16,
     is_flipped: bool,
     bus_grant_bkup: Option<bool>,
+    video_timing_changed: bool,
 }
 
 impl Mikey {
     pub fn new() -> Self {
-        Self {
+        let mut slf = Self {
             cpu: M6502::new(),
-            cpu_stepper: M6502Stepper::default(),
+            cpu_stepper: M6502Stepper::new(),
             cpu_pins: CPUPins::default(),
             uart: Uart::new(),
             ticks: 0,
@@ -87,31 +87,55 @@ impl Mikey {
             is_flipped: false,
             mikey_bus_owner: MikeyBusOwner::Cpu,
             bus_grant_bkup: None,
-        }
+            video_timing_changed: true,
+        };
+        slf.uart.set_bit_ticks(slf.comlynx_bit_ticks());
+        slf
     }
 
+    /* Back to the power on state of `new()`. The ComLynx links, color profile and ghosting are settings
+    of the emulator rather than of the console, they are kept. The UART is reset in place, a new one would
+    come with a cable of its own. */
     pub fn reset(&mut self) {
+        let color_profile = self.registers.color_profile();
+        let ghosting = self.video.ghosting();
         self.cpu = M6502::new();
-        self.cpu_stepper = M6502Stepper::default();
+        self.cpu_stepper = M6502Stepper::new();
         self.cpu_pins = CPUPins::default();
+        self.uart.reset();
         self.ticks = 0;
         self.timers = Timers::new();
         self.registers = MikeyRegisters::new();
+        self.registers.set_color_profile(color_profile);
         self.video = Video::new();
+        self.video.set_ghosting(ghosting);
         self.video_buffer_curr_addr = 0;
+        self.disp_addr = 0;
+        self.is_flipped = false;
         self.mikey_bus_owner = MikeyBusOwner::Cpu;
-        self.uart.reset();
+        self.bus_grant_bkup = None;
+        self.video_timing_changed = true;
+        self.uart.set_bit_ticks(self.comlynx_bit_ticks());
     }
 
-    pub fn cpu_prefetch(&mut self, pc: u16, rom: &mut Rom) {
+    pub fn cpu_prefetch(&mut self, pc: u16, opcode: u8) {
         trace!("- CPU prefetch 0x{:04x}", pc);
         self.cpu_pins.set(M6502_SYNC);
         self.cpu_pins.sa(pc);
-        self.cpu_pins.sd(rom.get(pc));
+        self.cpu_pins.sd(opcode);
         self.cpu.set_pc(pc);
         trace!("- CPU:{:?}", self.cpu);
     }
 
+    /* Register write applied at once, for states set up outside of the CPU. */
+    pub fn write(&mut self, addr: u16, data: u8, cart: &mut Cartridge) {
+        let mut bus = Bus::new();
+        bus.set_addr(addr);
+        bus.set_data(data);
+        self.poke(&bus);
+        self.process_ir_step(&mut bus, cart);
+    }
+
     pub fn cpu_tick(&mut self, bus: &mut Bus) {
         self.cpu_pins = self.cpu_stepper.tick(&mut self.cpu, self.cpu_pins);
         let addr = self.cpu_pins.ga();
@@ -132,7 +156,7 @@ impl Mikey {
         let (mut int, int4_triggered) = self.timers.tick_all(self.ticks);
 
         if int4_triggered { // "The interrupt bit for timer 4 (UART baud rate) is driven by receiver or transmitter ready bit of the UART."
-            if self.uart.tick(&mut self.registers) {
+            if self.uart.tick(&mut self.registers, self.ticks) {
                 int |= INT_TIMER4;
             }
         }
@@ -175,14 +199,6 @@ impl Mikey {
                     self.cpu_pins.pin_off(M6502_RDY);
                     trace!("[{}] < Poke, bus:{:?}", self.ticks, bus);
                 }
-                BusStatus::PokeIncCartRipple => {
-                    self.registers.set_ir(MikeyInstruction::PokeIncCartRipple);
-                    self.registers.set_ticks_delay(MIKEY_WRITE_TICKS);
-                }
-                BusStatus::PeekIncCartRipple => {
-                    self.registers.set_ir(MikeyInstruction::PeekIncCartRipple);
-                    self.registers.set_ticks_delay(MIKEY_WRITE_TICKS);
-                }
                 _ => ()
             }
 
@@ -418,6 +434,11 @@ impl Mikey {
             }
             MikeyInstruction::TimersPoke => { 
                 self.timers.poke(self.registers.addr_r(), self.registers.data_r() as u8); 
+                match self.registers.addr_r() {
+                    TIM0BKUP | TIM0CTLA | TIM2BKUP | TIM2CTLA => self.video_timing_changed = true,
+                    TIM4BKUP | TIM4CTLA => self.uart.set_bit_ticks(self.comlynx_bit_ticks()),
+                    _ => (),
+                }
                 bus.set_status(BusStatus::PokeDone); 
                 self.registers.reset_ir(); 
             }
@@ -456,18 +477,6 @@ impl Mikey {
                 self.registers.reset_ir(); 
                 bus.set_status(BusStatus::PokeDone); 
             }
-            MikeyInstruction::PokeIncCartRipple => {
-                self.registers.inc_cart_position();
-                cart.write_address_to_pins(self.registers.cart_shift(), self.registers.cart_position(), self.registers.audin());
-                self.registers.reset_ir(); 
-                bus.set_status(BusStatus::PokeDone); 
-            }
-            MikeyInstruction::PeekIncCartRipple => {
-                self.registers.inc_cart_position();
-                cart.write_address_to_pins(self.registers.cart_shift(), self.registers.cart_position(), self.registers.audin());
-                self.registers.reset_ir(); 
-                bus.set_status(BusStatus::PeekDone); 
-            }
             MikeyInstruction::CpuSleep => {
                 self.registers.reset_ir(); 
                 bus.set_grant(false);
@@ -511,7 +520,9 @@ impl Mikey {
                 bus.set_status(BusStatus::PokeDone); 
             }   
             MikeyInstruction::PokePbkup => { 
+                self.registers.set_data(PBKUP, self.registers.data_r() as u8);
                 self.video.set_pbkup(self.registers.data_r() as u8);
+                self.video_timing_changed = true;
                 self.registers.reset_ir(); 
                 bus.set_status(BusStatus::PokeDone); 
             }
@@ -530,9 +541,12 @@ impl Mikey {
         let new = self.registers.data_r() as u8;
         self.registers.set_data(SYSCTL1, new); 
         
-        if prev & SYSCTL1_POWER == 0 && new & SYSCTL1_POWER != 0 {
+        /* The ripple counter and the shift register lose their content with the cart power, their outputs
+        are low while it's off and they start again from 0 when it comes back. */
+        if (prev ^ new) & SYSCTL1_POWER != 0 {
             self.registers.reset_cart_position();
             self.registers.reset_cart_shift();
+            cart.write_address_to_pins(self.registers.cart_shift(), self.registers.cart_position(), self.registers.audin());
         }
 
         if new & SYSCTL1_POWER != 0 && prev & SYSCTL1_CAS != 0 && new & SYSCTL1_CAS == 0 {
@@ -608,11 +622,62 @@ impl Mikey {
         &self.video
     }
 
-    #[cfg(not(feature = "comlynx_shared_memory"))]
+    pub fn video_timing(&self) -> VideoTiming {
+        VideoTiming::new(
+            self.timers.peek(TIM0CTLA),
+            self.timers.peek(TIM0BKUP),
+            self.timers.peek(TIM2BKUP),
+            self.registers.data(PBKUP),
+        )
+    }
+
+    pub fn video_timing_changed(&mut self) -> bool {
+        if self.video_timing_changed {
+            self.video_timing_changed = false;
+            true
+        } else {
+            false
+        }
+    }
+
+    /* "
+    The baud rate is generated by TIMER4 according to the equation
+    CLOCK4 / (TIMER4 + 1) / 8
+    " */
+    pub fn comlynx_bit_ticks(&self) -> u64 {
+        match self.timers.peek(TIM4CTLA) & 0b111 {
+            7 => 0, // linked, timer 4 has no source
+            period => (self.timers.peek(TIM4BKUP) as u64 + 1) * 8 * TIMER_TICKS_COUNT as u64 * u64::pow(2, period as u32),
+        }
+    }
+
+    pub fn comlynx_baud_rate(&self) -> u32 {
+        match self.comlynx_bit_ticks() {
+            0 => 0,
+            t => (CRYSTAL_FREQ as u64 / t) as u32,
+        }
+    }
+
     pub fn set_comlynx_cable(&mut self, cable: &ComlynxCable) {
         self.uart.set_cable(cable);
     }
     
+    pub fn set_comlynx_transport(&mut self, transport: Option<Box<dyn ComlynxTransport>>) {
+        self.uart.set_transport(transport);
+    }
+
+    pub fn set_comlynx_capture(&mut self, capture: Option<&ComlynxCapture>, sender: u8) {
+        self.uart.set_capture(capture, sender);
+    }
+
+    pub fn set_comlynx_fault(&mut self, fault: Option<Box<dyn ComlynxFault>>) {
+        self.uart.set_fault(fault);
+    }
+
+    pub(crate) fn take_comlynx_links(&mut self, other: &mut Mikey) {
+        self.uart.take_links(&mut other.uart);
+    }
+
     pub(crate) fn comlynx_cable(&self) -> &ComlynxCable {
         self.uart.cable()
     }
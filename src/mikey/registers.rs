@@ -72,7 +72,7 @@ bitflags! {
     }
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct MikeyRegisters {
     ticks_delay: u16,
     data_r: u16,
@@ -89,6 +89,8 @@ pub struct MikeyRegisters {
     palette: [[u8; 3]; 16],
     attenuation_left: [f32; 4],
     attenuation_right: [f32; 4],
+    #[serde(skip)]
+    color_profile: ColorProfile,
 }
 
 impl MikeyRegisters {
@@ -109,6 +111,7 @@ impl MikeyRegisters {
             palette: Default::default(),
             attenuation_left: [0.; 4],
             attenuation_right: [0.; 4],
+            color_profile: ColorProfile::default(),
         };
         for i in 0..16 {
             slf.set_data(GREEN0+i, 0xff);
@@ -171,9 +174,18 @@ impl MikeyRegisters {
     fn update_pen(&mut self, pen_index: u16) {
         let bluered = self.data(BLUERED0+pen_index);
         let green = self.data(GREEN0+pen_index);
-        self.palette[pen_index as usize][0] = (bluered & 0xf) * 16;
-        self.palette[pen_index as usize][1] = (green & 0xf) * 16;
-        self.palette[pen_index as usize][2] = (bluered >> 4) * 16;
+        self.palette[pen_index as usize] = self.color_profile.rgb(bluered & 0xf, green & 0xf, bluered >> 4);
+    }
+
+    pub fn color_profile(&self) -> ColorProfile {
+        self.color_profile
+    }
+
+    pub fn set_color_profile(&mut self, profile: ColorProfile) {
+        self.color_profile = profile;
+        for i in 0..16 {
+            self.update_pen(i);
+        }
     }
 
     #[inline]
@@ -262,7 +274,7 @@ impl MikeyRegisters {
         };
 
         if brk && !self.serctl_w_is_flag_set(SerCtlW::tx_brk) { //Set redeye to high if break has been disabled
-            uart.set_redeye_pin(uart::redeye_status::RedeyeStatus::High);
+            uart.set_break(false);
         }
 
         if self.serctl_w_is_flag_set(SerCtlW::reset_err) {
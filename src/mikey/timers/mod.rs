@@ -8,7 +8,7 @@ use base_timer::BaseTimer;
 use core::num::NonZeroU8;
 use log::trace;
 
-const TIMER_TICKS_COUNT: u16 = (0.000001 / CRYSTAL_TICK_LENGTH) as u16; // 1us/62.5ns
+pub const TIMER_TICKS_COUNT: u16 = (0.000001 / CRYSTAL_TICK_LENGTH) as u16; // 1us/62.5ns
 
 const TIMER_LINKS: [Option<NonZeroU8>; 12] = [
     NonZeroU8::new(2),
@@ -57,7 +57,7 @@ pub enum TimerReg {
     ShiftRegister,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Timers {
     timers: [TimerType; TIMER_COUNT as usize],
     timers_triggered: [bool; 8],
@@ -283,7 +283,7 @@ impl Timers {
                 _ => unreachable!(),
             },
             TimerType::Audio(t) => {
-                let settings = &mut self.audio_settings[index];
+                let settings = &mut self.audio_settings[index - 8];
                 match cmd {
                     TimerReg::Backup => {
                         t.set_backup(v);
@@ -342,3 +342,22 @@ impl Default for Timers {
         Timers::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn audio_registers_round_trip() {
+        let mut timers = Timers::new();
+        for (ch, base) in [AUD0VOL, AUD1VOL, AUD2VOL, AUD3VOL].into_iter().enumerate() {
+            timers.poke(base, 0x10 + ch as u8);
+            timers.poke(base + 2, 0x20 + ch as u8);
+        }
+        for (ch, base) in [AUD0VOL, AUD1VOL, AUD2VOL, AUD3VOL].into_iter().enumerate() {
+            assert_eq!(timers.peek(base), 0x10 + ch as u8);
+            assert_eq!(timers.peek(base + 2), 0x20 + ch as u8);
+            assert_eq!(timers.audio_out(8 + ch), 0x20 + ch as i16);
+        }
+    }
+}
@@ -0,0 +1,234 @@
+use alloc::sync::Arc;
+use log::trace;
+use parking_lot::Mutex;
+use redeye_status::RedeyeStatus;
+use super::comlynx_cable_mutex::ComlynxCable;
+use super::*;
+
+pub const COMLYNX_BUS_MAX_UNITS: usize = 8;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Drive {
+    Released,
+    Driven(RedeyeStatus),
+}
+
+struct BusState {
+    attached: [bool; COMLYNX_BUS_MAX_UNITS],
+    drives: [Drive; COMLYNX_BUS_MAX_UNITS],
+    in_collision: bool,
+    collisions: u64,
+    last_collision_units: u8,
+}
+
+impl BusState {
+    fn level(&mut self) -> RedeyeStatus {
+        let mut low = 0u8;
+        let mut high = 0u8;
+        for (unit, drive) in self.drives.iter().enumerate() {
+            match drive {
+                Drive::Driven(RedeyeStatus::Low) => low |= 1 << unit,
+                Drive::Driven(RedeyeStatus::High) => high |= 1 << unit,
+                Drive::Released => (),
+            }
+        }
+
+        /* A unit sending a 1 while another one pulls the line low lost its bit. */
+        let in_collision = low != 0 && high != 0;
+        if in_collision && !self.in_collision {
+            trace!("ComLynx collision, units:{:08b}", low | high);
+            self.collisions += 1;
+            self.last_collision_units = low | high;
+        }
+        self.in_collision = in_collision;
+
+        if low != 0 { RedeyeStatus::Low } else { RedeyeStatus::High }
+    }
+}
+
+/* Open-collector ComLynx wire: the line is low as soon as one attached unit pulls it low. */
+#[derive(Clone)]
+pub struct ComlynxBus {
+    state: Arc<Mutex<BusState>>,
+}
+
+impl ComlynxBus {
+    pub fn new() -> Self {
+        Self {
+            state: Arc::new(Mutex::new(BusState {
+                attached: [false; COMLYNX_BUS_MAX_UNITS],
+                drives: [Drive::Released; COMLYNX_BUS_MAX_UNITS],
+                in_collision: false,
+                collisions: 0,
+                last_collision_units: 0,
+            })),
+        }
+    }
+
+    /* Returns the cable of a new unit. The unit stays attached until every clone of its cable is dropped. */
+    pub fn attach(&self) -> Result<ComlynxCable, &'static str> {
+        let mut state = self.state.lock();
+        match state.attached.iter().position(|a| !a) {
+            None => Err("ComLynx bus is full."),
+            Some(unit) => {
+                state.attached[unit] = true;
+                state.drives[unit] = Drive::Released;
+                Ok(ComlynxCable::from_bus_tap(BusTap { unit: Arc::new(BusUnit { bus: self.clone(), unit }) }))
+            }
+        }
+    }
+
+    pub fn attached_units(&self) -> usize {
+        self.state.lock().attached.iter().filter(|a| **a).count()
+    }
+
+    pub fn status(&self) -> RedeyeStatus {
+        self.state.lock().level()
+    }
+
+    pub fn collisions(&self) -> u64 {
+        self.state.lock().collisions
+    }
+
+    /* Bit mask of the units driving the line when the last collision happened. */
+    pub fn last_collision_units(&self) -> u8 {
+        self.state.lock().last_collision_units
+    }
+}
+
+impl Default for ComlynxBus {
+    fn default() -> Self {
+        ComlynxBus::new()
+    }
+}
+
+struct BusUnit {
+    bus: ComlynxBus,
+    unit: usize,
+}
+
+impl Drop for BusUnit {
+    fn drop(&mut self) {
+        let mut state = self.bus.state.lock();
+        state.attached[self.unit] = false;
+        state.drives[self.unit] = Drive::Released;
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct BusTap {
+    unit: Arc<BusUnit>,
+}
+
+impl BusTap {
+    pub fn unit(&self) -> usize {
+        self.unit.unit
+    }
+
+    pub fn status(&self) -> RedeyeStatus {
+        self.unit.bus.state.lock().level()
+    }
+
+    pub fn set(&self, status: RedeyeStatus) {
+        self.unit.bus.state.lock().drives[self.unit.unit] = Drive::Driven(status);
+    }
+
+    pub fn release(&self) {
+        self.unit.bus.state.lock().drives[self.unit.unit] = Drive::Released;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wired_and() {
+        let bus = ComlynxBus::new();
+        let mut a = bus.attach().unwrap();
+        let mut b = bus.attach().unwrap();
+        let c = bus.attach().unwrap();
+        assert_eq!(bus.attached_units(), 3);
+
+        a.set(RedeyeStatus::Low);
+        assert!(c.status() == RedeyeStatus::Low);
+        b.set(RedeyeStatus::Low);
+        a.release();
+        assert!(c.status() == RedeyeStatus::Low);
+        b.release();
+        assert!(c.status() == RedeyeStatus::High);
+        assert_eq!(bus.collisions(), 0);
+    }
+
+    #[test]
+    fn collision_reported_once() {
+        let bus = ComlynxBus::new();
+        let mut a = bus.attach().unwrap();
+        let mut b = bus.attach().unwrap();
+
+        a.set(RedeyeStatus::High);
+        b.set(RedeyeStatus::Low);
+        assert!(a.status() == RedeyeStatus::Low);
+        assert!(b.status() == RedeyeStatus::Low);
+        assert_eq!(bus.collisions(), 1);
+        assert_eq!(bus.last_collision_units(), 0b11);
+
+        a.release();
+        assert!(a.status() == RedeyeStatus::Low);
+        assert_eq!(bus.collisions(), 1);
+    }
+
+    #[test]
+    fn eight_units_max() {
+        let bus = ComlynxBus::new();
+        let cables: alloc::vec::Vec<_> = (0..COMLYNX_BUS_MAX_UNITS).map(|_| bus.attach().unwrap()).collect();
+        assert!(bus.attach().is_err());
+
+        // clones are the same unit
+        let clone = cables[3].clone();
+        assert_eq!(clone.bus_unit(), Some(3));
+        drop(cables);
+        assert_eq!(bus.attached_units(), 1);
+        assert_eq!(bus.attach().unwrap().bus_unit(), Some(0));
+    }
+
+    #[test]
+    fn uarts_on_bus() {
+        let bus = ComlynxBus::new();
+        let mut units: alloc::vec::Vec<(Uart, MikeyRegisters)> = (0..3).map(|_| {
+            let mut uart = Uart::new();
+            let mut regs = MikeyRegisters::new();
+            uart.set_cable(&bus.attach().unwrap());
+            regs.set_serctl(&mut uart, SerCtlW::tx_open.bits());
+            (uart, regs)
+        }).collect();
+        assert_eq!(bus.attached_units(), 3);
+
+        let run = |units: &mut alloc::vec::Vec<(Uart, MikeyRegisters)>| {
+            let mut received = vec![vec![]; units.len()];
+            for tick in 0..300 {
+                for (i, (uart, regs)) in units.iter_mut().enumerate() {
+                    uart.tick(regs, tick);
+                    if regs.serctl_r_is_flag_set(SerCtlR::rx_rdy) {
+                        received[i].push(uart.get_data(regs));
+                    }
+                }
+            }
+            received
+        };
+
+        let (uart, regs) = &mut units[1];
+        uart.set_transmit_holding_buffer(regs, 0x3C);
+        assert_eq!(run(&mut units), vec![vec![0x3C]; 3]);
+        assert_eq!(bus.collisions(), 0);
+
+        for (data, (uart, regs)) in [0x0F, 0xF0].into_iter().zip(units.iter_mut()) {
+            uart.set_transmit_holding_buffer(regs, data);
+        }
+        // every unit hears a mangled byte, the exact value depends on the order units are ticked
+        let received = run(&mut units);
+        assert!(received.iter().all(|r| r.len() == 1 && r[0] != 0x0F && r[0] != 0xF0));
+        assert!(bus.collisions() > 0);
+        assert_eq!(bus.last_collision_units(), 0b011);
+    }
+}
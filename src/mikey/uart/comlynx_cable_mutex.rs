@@ -3,27 +3,57 @@ use parking_lot::Mutex;
 use redeye_status::RedeyeStatus;
 use serde::{de::{self, Visitor}, Deserializer, Serializer};
 
+use super::comlynx_bus::BusTap;
 use super::*;
 
+/* Point to point cable, the last unit setting the level wins. Cables attached to a `ComlynxBus` resolve
+the levels of every unit instead. */
 pub struct ComlynxCable {
     redeye_pin: Arc<Mutex<RedeyeStatus>>,
+    tap: Option<BusTap>,
 }
 
 impl ComlynxCable {
     pub fn new(cable: Option<Arc<Mutex<RedeyeStatus>>>) -> Self {
         if let Some(redeye_pin) = cable {
-            Self { redeye_pin }
+            Self { redeye_pin, tap: None }
         } else {
-            Self { redeye_pin: Arc::new(Mutex::new(RedeyeStatus::High)) }
+            Self { redeye_pin: Arc::new(Mutex::new(RedeyeStatus::High)), tap: None }
         }        
     }
 
+    pub(crate) fn from_bus_tap(tap: BusTap) -> Self {
+        Self { redeye_pin: Arc::new(Mutex::new(RedeyeStatus::High)), tap: Some(tap) }
+    }
+
+    pub fn bus_unit(&self) -> Option<usize> {
+        self.tap.as_ref().map(|t| t.unit())
+    }
+
     pub fn status(&self) -> RedeyeStatus {
-        *self.redeye_pin.lock()
+        match &self.tap {
+            None => *self.redeye_pin.lock(),
+            Some(t) => t.status(),
+        }
     }
 
     pub fn set(&mut self, status: RedeyeStatus) {
-        *self.redeye_pin.lock() = status;
+        match &self.tap {
+            None => *self.redeye_pin.lock() = status,
+            Some(t) => t.set(status),
+        }
+    }
+
+    /* A cable of its own holding the current level, connected to nothing. */
+    pub fn detached(&self) -> Self {
+        ComlynxCable::new(Some(Arc::new(Mutex::new(self.status()))))
+    }
+
+    /* Stops driving the line. A point to point cable keeps its last level. */
+    pub fn release(&mut self) {
+        if let Some(t) = &self.tap {
+            t.release();
+        }
     }
 }
 
@@ -36,7 +66,8 @@ impl Default for ComlynxCable {
 impl Clone for ComlynxCable {
     fn clone(&self) -> Self {
         Self { 
-            redeye_pin: self.redeye_pin.clone()
+            redeye_pin: self.redeye_pin.clone(),
+            tap: self.tap.clone(),
         }
     }
 }
@@ -46,7 +77,7 @@ impl Serialize for ComlynxCable {
     where
         S: Serializer,
     {
-        let v = *self.redeye_pin.lock() as u8;
+        let v = self.status() as u8;
         serializer.serialize_u8(v)
     }
 }
@@ -1,48 +1,172 @@
-use alloc::fmt;
+use alloc::{fmt, format, string::String};
+use core::sync::atomic::{AtomicU8, Ordering};
+use log::{error, trace};
 use redeye_status::RedeyeStatus;
 use serde::{de::{self, Visitor}, Deserializer, Serializer};
 use ::shared_memory::{Shmem, ShmemConf, ShmemError};
+use std::path::PathBuf;
 
 use super::*;
 
+pub const DEFAULT_CABLE_NAME: &str = "redeye";
+const CABLE_SHMEM_SIZE: usize = 32;
+
+/* Drive of a unit on the wire, one byte of the segment per plugged cable. */
+const SLOT_FREE: u8 = 0;
+const SLOT_RELEASED: u8 = 1;
+const SLOT_LOW: u8 = 2;
+
+/* Redeye wire shared between processes through a named shared memory segment.
+The file link lives in the system temporary directory and is named after the cable name and session id,
+so independent sessions can coexist on the same host. Every plugged cable owns a slot of the segment,
+the wire is low as long as one of them pulls it low.
+The default cable, a cable that couldn't be cloned, or one that comes from a deserialized state, is
+unplugged: it only holds its own level. `new()` and `with_session()` plug a cable and report why they
+couldn't, hand it to the console with `set_comlynx_cable`. */
 pub struct ComlynxCable {
-    shmem: Shmem,
+    shmem: Option<Shmem>,
+    slot: usize,
+    name: String,
+    session: u32,
+    status: RedeyeStatus,
 }
 
 impl ComlynxCable {
-    pub fn new() -> Self {
-        let shmem = match ShmemConf::new().size(32).flink("redeye").create() {
+    pub fn new() -> Result<Self, &'static str> {
+        Self::with_name(DEFAULT_CABLE_NAME)
+    }
+
+    pub fn with_name(name: &str) -> Result<Self, &'static str> {
+        Self::with_session(name, 0)
+    }
+
+    pub fn with_session(name: &str, session: u32) -> Result<Self, &'static str> {
+        if name.is_empty() || name.contains(['/', '\\']) {
+            return Err("Invalid ComLynx cable name.");
+        }
+        let shmem = Self::plug(&Self::flink_path(name, session))?;
+        let Some(slot) = Self::slots(&shmem).iter().position(|s| s.compare_exchange(SLOT_FREE, SLOT_RELEASED, Ordering::SeqCst, Ordering::SeqCst).is_ok()) else {
+            return Err("Too many units on the ComLynx cable.");
+        };
+        Ok(Self { shmem: Some(shmem), slot, name: name.into(), session, status: RedeyeStatus::High })
+    }
+
+    fn unplugged(status: RedeyeStatus) -> Self {
+        Self { shmem: None, slot: 0, name: String::new(), session: 0, status }
+    }
+
+    fn slots(shmem: &Shmem) -> &[AtomicU8] {
+        unsafe { core::slice::from_raw_parts(shmem.as_ptr() as *const AtomicU8, CABLE_SHMEM_SIZE) }
+    }
+
+    fn clear(shmem: &Shmem) {
+        for s in Self::slots(shmem) {
+            s.store(SLOT_FREE, Ordering::SeqCst);
+        }
+    }
+
+    pub fn flink_path(name: &str, session: u32) -> PathBuf {
+        std::env::temp_dir().join(format!("holani-{}-{}", name, session))
+    }
+
+    fn plug(flink: &PathBuf) -> Result<Shmem, &'static str> {
+        match ShmemConf::new().size(CABLE_SHMEM_SIZE).flink(flink).create() {
             Ok(m) => {
-                unsafe { *m.as_ptr() = RedeyeStatus::High.into() };
-                m
+                Self::clear(&m);
+                Ok(m)
             },
-            Err(ShmemError::LinkExists) => match ShmemConf::new().flink("redeye").open() {
-                Ok(s) => s,
-                Err(_) => match ShmemConf::new().size(32).flink("redeye").force_create_flink().create() {
-                    Ok(m) => {
-                        unsafe { *m.as_ptr() = RedeyeStatus::High.into() };
-                        m
-                    },
-                    Err(e) => panic!("Unable to create or open shmem flink 'redeye' : {}", e),
+            Err(ShmemError::LinkExists) => match ShmemConf::new().flink(flink).open() {
+                Ok(s) => Ok(s),
+                Err(_) => {
+                    // the link outlived the process which created the segment
+                    trace!("Replacing stale ComLynx flink {:?}", flink);
+                    match ShmemConf::new().size(CABLE_SHMEM_SIZE).flink(flink).force_create_flink().create() {
+                        Ok(m) => {
+                            Self::clear(&m);
+                            Ok(m)
+                        },
+                        Err(_) => Err("Unable to replace stale ComLynx shared memory."),
+                    }
                 }
             },
-            Err(e) => panic!("Unable to create or open shmem flink 'redeye' : {}", e)
-        };
-        ComlynxCable { shmem }
+            Err(_) => Err("Unable to create or open ComLynx shared memory."),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn session(&self) -> u32 {
+        self.session
+    }
+
+    pub fn is_plugged(&self) -> bool {
+        self.shmem.is_some()
     }
 
     pub fn status(&self) -> RedeyeStatus {
-        unsafe { (*self.shmem.as_ptr()).into() }
+        match &self.shmem {
+            None => self.status,
+            Some(m) => match Self::slots(m).iter().any(|s| s.load(Ordering::SeqCst) == SLOT_LOW) {
+                true => RedeyeStatus::Low,
+                false => RedeyeStatus::High,
+            },
+        }
     }
 
     pub fn set(&mut self, status: RedeyeStatus) {
-        unsafe { *self.shmem.as_ptr() = status.into() };
+        match &self.shmem {
+            None => self.status = status,
+            Some(m) => Self::slots(m)[self.slot].store(if status == RedeyeStatus::Low { SLOT_LOW } else { SLOT_RELEASED }, Ordering::SeqCst),
+        }
+    }
+
+    /* An unplugged cable holding the current level. */
+    pub fn detached(&self) -> Self {
+        ComlynxCable::unplugged(self.status())
+    }
+
+    /* Stops driving the line, the other units set its level. An unplugged cable keeps its last level. */
+    pub fn release(&mut self) {
+        if let Some(m) = &self.shmem {
+            Self::slots(m)[self.slot].store(SLOT_RELEASED, Ordering::SeqCst);
+        }
+    }
+
+    /* Another cable plugged on the same wire, with a slot of its own. */
+    pub fn try_clone(&self) -> Result<Self, &'static str> {
+        match self.shmem {
+            None => Ok(ComlynxCable::unplugged(self.status)),
+            Some(_) => ComlynxCable::with_session(&self.name, self.session),
+        }
+    }
+}
+
+impl Drop for ComlynxCable {
+    fn drop(&mut self) {
+        if let Some(m) = &self.shmem {
+            Self::slots(m)[self.slot].store(SLOT_FREE, Ordering::SeqCst);
+        }
     }
 }
 
 impl Default for ComlynxCable {
     fn default() -> Self {
-        ComlynxCable::new()
+        ComlynxCable::unplugged(RedeyeStatus::High)
+    }
+}
+
+/* A clone that can't be plugged is unplugged, `try_clone()` reports the error instead. */
+impl Clone for ComlynxCable {
+    fn clone(&self) -> Self {
+        match self.try_clone() {
+            Ok(c) => c,
+            Err(e) => {
+                error!("{}", e);
+                ComlynxCable::unplugged(self.status())
+            }
+        }
     }
 }
 
@@ -51,8 +175,7 @@ impl Serialize for ComlynxCable {
     where
         S: Serializer,
     {
-        let v = unsafe { *self.shmem.as_ptr() };
-        serializer.serialize_u8(v)
+        serializer.serialize_u8(self.status().into())
     }
 }
 
@@ -71,12 +194,11 @@ impl Visitor<'_> for ComlynxCableVisitor {
         formatter.write_str("an u8")
     }
 
-    fn visit_u8<E>(self, _value: u8) -> Result<Self::Value, E>
+    fn visit_u8<E>(self, value: u8) -> Result<Self::Value, E>
     where
         E: de::Error,
     {
-        let cable = ComlynxCable::new();
-        Ok(cable)
+        Ok(ComlynxCable::unplugged(value.into()))
     }
 }
 
@@ -87,4 +209,60 @@ impl<'de> Deserialize<'de> for ComlynxCable {
     {
         deserializer.deserialize_u8(ComlynxCableVisitor::new())
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sessions_are_independent() {
+        let mut a = ComlynxCable::with_session("test-sessions", 1).unwrap();
+        let b = ComlynxCable::with_session("test-sessions", 1).unwrap();
+        let c = ComlynxCable::with_session("test-sessions", 2).unwrap();
+        a.set(RedeyeStatus::Low);
+        assert!(b.status() == RedeyeStatus::Low);
+        assert!(c.status() == RedeyeStatus::High);
+        assert!(a.clone().status() == RedeyeStatus::Low);
+    }
+
+    #[test]
+    fn release() {
+        let mut a = ComlynxCable::with_session("test-release", 0).unwrap();
+        let mut b = ComlynxCable::with_session("test-release", 0).unwrap();
+        a.set(RedeyeStatus::Low);
+        b.set(RedeyeStatus::High);
+        // open collector, a unit pulling the line low wins
+        assert!(b.status() == RedeyeStatus::Low);
+        a.release();
+        assert!(b.status() == RedeyeStatus::High);
+
+        b.set(RedeyeStatus::Low);
+        assert!(a.status() == RedeyeStatus::Low);
+        drop(b);
+        assert!(a.status() == RedeyeStatus::High);
+    }
+
+    #[test]
+    fn unplugged_by_default() {
+        let mut cable = ComlynxCable::default();
+        assert!(!cable.is_plugged());
+        cable.set(RedeyeStatus::Low);
+        assert!(cable.try_clone().unwrap().status() == RedeyeStatus::Low);
+    }
+
+    #[test]
+    fn stale_flink_replaced() {
+        let flink = ComlynxCable::flink_path("test-stale", 0);
+        std::fs::write(&flink, "/shmem_dead").unwrap();
+        let cable = ComlynxCable::with_name("test-stale").unwrap();
+        assert!(cable.is_plugged());
+        assert!(cable.status() == RedeyeStatus::High);
+    }
+
+    #[test]
+    fn invalid_name() {
+        assert!(ComlynxCable::with_name("a/b").is_err());
+        assert!(ComlynxCable::with_name("").is_err());
+    }
+}
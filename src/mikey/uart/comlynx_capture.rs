@@ -0,0 +1,176 @@
+use alloc::{collections::VecDeque, sync::Arc, vec::Vec};
+use bitflags::bitflags;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use crate::consts::CRYSTAL_FREQ;
+use super::comlynx_transport::{ComlynxFrame, ComlynxPacket, ComlynxTransport};
+
+const PCAP_MAGIC: u32 = 0xa1b2c3d4;
+const PCAP_HEADER_LEN: usize = 24;
+const PCAP_RECORD_HEADER_LEN: usize = 16;
+const PCAP_LINKTYPE_USER0: u32 = 147;
+const CAPTURE_RECORD_LEN: usize = 12;
+
+bitflags! {
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+    pub struct CaptureFlags:u8
+    {
+        const parity_bit = 0b00000001;
+        const parity_err = 0b00000010;
+        const frame_err  = 0b00000100;
+        const overrun    = 0b00001000;
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum CaptureKind {
+    Transmit = 0,
+    Receive,
+    BreakStart,
+    BreakEnd,
+    BreakReceived,
+}
+
+impl TryFrom<u8> for CaptureKind {
+    type Error = &'static str;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(CaptureKind::Transmit),
+            1 => Ok(CaptureKind::Receive),
+            2 => Ok(CaptureKind::BreakStart),
+            3 => Ok(CaptureKind::BreakEnd),
+            4 => Ok(CaptureKind::BreakReceived),
+            _ => Err("Unknown capture record kind."),
+        }
+    }
+}
+
+/* `flags` are the parity bit sent or received, and the errors found in that frame alone. */
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct CaptureRecord {
+    pub tick: u64,
+    pub sender: u8,
+    pub kind: CaptureKind,
+    pub data: u8,
+    pub flags: CaptureFlags,
+}
+
+/* ComLynx sniffer, clones share the same record list so every unit of a session can log into one capture. */
+#[derive(Clone, Default)]
+pub struct ComlynxCapture {
+    records: Arc<Mutex<Vec<CaptureRecord>>>,
+}
+
+impl ComlynxCapture {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&self, record: CaptureRecord) {
+        self.records.lock().push(record);
+    }
+
+    pub fn records(&self) -> Vec<CaptureRecord> {
+        self.records.lock().clone()
+    }
+
+    pub fn clear(&self) {
+        self.records.lock().clear();
+    }
+
+    /* pcap file with the LINKTYPE_USER0 link type, timestamps are derived from the Lynx ticks. */
+    pub fn to_pcap(&self) -> Vec<u8> {
+        let records = self.records.lock();
+        let mut out = Vec::with_capacity(PCAP_HEADER_LEN + records.len() * (PCAP_RECORD_HEADER_LEN + CAPTURE_RECORD_LEN));
+        out.extend_from_slice(&PCAP_MAGIC.to_le_bytes());
+        out.extend_from_slice(&2u16.to_le_bytes());
+        out.extend_from_slice(&4u16.to_le_bytes());
+        out.extend_from_slice(&0i32.to_le_bytes());
+        out.extend_from_slice(&0u32.to_le_bytes());
+        out.extend_from_slice(&(CAPTURE_RECORD_LEN as u32).to_le_bytes());
+        out.extend_from_slice(&PCAP_LINKTYPE_USER0.to_le_bytes());
+
+        for r in records.iter() {
+            let seconds = r.tick / CRYSTAL_FREQ as u64;
+            let micros = (r.tick % CRYSTAL_FREQ as u64) * 1_000_000 / CRYSTAL_FREQ as u64;
+            out.extend_from_slice(&(seconds as u32).to_le_bytes());
+            out.extend_from_slice(&(micros as u32).to_le_bytes());
+            out.extend_from_slice(&(CAPTURE_RECORD_LEN as u32).to_le_bytes());
+            out.extend_from_slice(&(CAPTURE_RECORD_LEN as u32).to_le_bytes());
+            out.extend_from_slice(&r.tick.to_le_bytes());
+            out.extend_from_slice(&[r.sender, r.kind as u8, r.data, r.flags.bits()]);
+        }
+        out
+    }
+
+    pub fn from_pcap(data: &[u8]) -> Result<Vec<CaptureRecord>, &'static str> {
+        if data.len() < PCAP_HEADER_LEN || data[..4] != PCAP_MAGIC.to_le_bytes() {
+            return Err("Not a ComLynx capture.");
+        }
+        if data[20..24] != PCAP_LINKTYPE_USER0.to_le_bytes() {
+            return Err("Unsupported capture link type.");
+        }
+
+        let mut records = vec![];
+        let mut pos = PCAP_HEADER_LEN;
+        while pos < data.len() {
+            let payload = pos + PCAP_RECORD_HEADER_LEN;
+            if data.len() < payload + CAPTURE_RECORD_LEN {
+                return Err("Truncated capture.");
+            }
+            let len = u32::from_le_bytes(data[pos + 8..pos + 12].try_into().unwrap()) as usize;
+            if len != CAPTURE_RECORD_LEN {
+                return Err("Invalid capture record.");
+            }
+            let r = &data[payload..payload + CAPTURE_RECORD_LEN];
+            records.push(CaptureRecord {
+                tick: u64::from_le_bytes(r[..8].try_into().unwrap()),
+                sender: r[8],
+                kind: CaptureKind::try_from(r[9])?,
+                data: r[10],
+                flags: CaptureFlags::from_bits_truncate(r[11]),
+            });
+            pos = payload + CAPTURE_RECORD_LEN;
+        }
+        Ok(records)
+    }
+}
+
+/* Transport replaying the transmissions of the other units of a capture, what the local unit sends is dropped.
+Plugged into a single `Lynx` it reproduces the traffic it received during the captured session. */
+pub struct ComlynxReplay {
+    packets: VecDeque<ComlynxPacket>,
+}
+
+impl ComlynxReplay {
+    pub fn new(records: &[CaptureRecord], local_sender: u8) -> Self {
+        let packets = records
+            .iter()
+            .filter(|r| r.sender != local_sender)
+            .filter_map(|r| {
+                let frame = match r.kind {
+                    CaptureKind::Transmit => ComlynxFrame::Data { data: r.data, parity: r.flags.contains(CaptureFlags::parity_bit) },
+                    CaptureKind::BreakStart => ComlynxFrame::BreakStart,
+                    CaptureKind::BreakEnd => ComlynxFrame::BreakEnd,
+                    CaptureKind::Receive | CaptureKind::BreakReceived => return None,
+                };
+                Some(ComlynxPacket { tick: r.tick, frame })
+            })
+            .collect();
+        Self { packets }
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.packets.len()
+    }
+}
+
+impl ComlynxTransport for ComlynxReplay {
+    fn send(&mut self, _packet: ComlynxPacket) {}
+
+    fn receive(&mut self, _current_tick: u64) -> Option<ComlynxPacket> {
+        self.packets.pop_front()
+    }
+}
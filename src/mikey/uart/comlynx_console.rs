@@ -0,0 +1,148 @@
+use alloc::boxed::Box;
+use super::comlynx_transport::{ComlynxFrame, ComlynxPacket, ComlynxTransport};
+use super::FRAME_BITS;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum ConsoleParity {
+    /* Parity computed like the Lynx does with parity enabled. */
+    #[default]
+    Computed,
+    /* Parity bit forced high or low, for games leaving parity disabled. */
+    Fixed(bool),
+}
+
+impl ConsoleParity {
+    pub fn bit(&self, data: u8) -> bool {
+        match self {
+            ConsoleParity::Computed => data.count_ones() & 1 == 1,
+            ConsoleParity::Fixed(p) => *p,
+        }
+    }
+}
+
+/* Serial line bridged to the host, e.g. for BLL style debug output. Bytes sent by the game are handed to `write`,
+bytes returned by `read` are fed to the game at the baud rate it programmed in TIMER4. */
+pub struct ComlynxConsole {
+    read: Box<dyn FnMut() -> Option<u8> + Send>,
+    write: Box<dyn FnMut(u8) + Send>,
+    parity: ConsoleParity,
+    bit_ticks: u64,
+    next_tick: u64,
+}
+
+impl ComlynxConsole {
+    pub fn new(read: Box<dyn FnMut() -> Option<u8> + Send>, write: Box<dyn FnMut(u8) + Send>) -> Self {
+        Self {
+            read,
+            write,
+            parity: ConsoleParity::default(),
+            bit_ticks: 0,
+            next_tick: 0,
+        }
+    }
+
+    pub fn with_parity(mut self, parity: ConsoleParity) -> Self {
+        self.parity = parity;
+        self
+    }
+
+    pub fn bit_ticks(&self) -> u64 {
+        self.bit_ticks
+    }
+}
+
+impl ComlynxTransport for ComlynxConsole {
+    fn send(&mut self, packet: ComlynxPacket) {
+        if let ComlynxFrame::Data { data, .. } = packet.frame {
+            (self.write)(data);
+        }
+    }
+
+    fn receive(&mut self, current_tick: u64) -> Option<ComlynxPacket> {
+        if current_tick < self.next_tick {
+            return None;
+        }
+        let data = (self.read)()?;
+        self.next_tick = current_tick + FRAME_BITS as u64 * self.bit_ticks;
+        Some(ComlynxPacket { tick: current_tick, frame: ComlynxFrame::Data { data, parity: self.parity.bit(data) } })
+    }
+
+    fn set_bit_ticks(&mut self, bit_ticks: u64) {
+        self.bit_ticks = bit_ticks;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{collections::VecDeque, sync::Arc, vec::Vec};
+    use parking_lot::Mutex;
+    use super::*;
+    use super::super::*;
+
+    #[test]
+    fn console_bridge() {
+        let input = Arc::new(Mutex::new(VecDeque::from([b'h', b'i', b'!'])));
+        let output = Arc::new(Mutex::new(Vec::new()));
+        let (i, o) = (input.clone(), output.clone());
+        let console = ComlynxConsole::new(
+            Box::new(move || i.lock().pop_front()),
+            Box::new(move |data| o.lock().push(data)),
+        );
+
+        let mut uart = Uart::new();
+        let mut regs = MikeyRegisters::new();
+        // one bit every 8 calls to `tick` in this test timeline
+        uart.set_bit_ticks(8);
+        uart.set_transport(Some(Box::new(console)));
+        regs.set_serctl(&mut uart, (SerCtlW::par_en | SerCtlW::tx_open).bits());
+        uart.set_transmit_holding_buffer(&mut regs, b'>');
+
+        let mut received = vec![];
+        for tick in 0..1000 {
+            uart.tick(&mut regs, tick);
+            if regs.serctl_r_is_flag_set(SerCtlR::rx_rdy) {
+                received.push(uart.get_data(&mut regs));
+                assert!(!regs.serctl_r_is_flag_set(SerCtlR::par_err));
+                assert!(!regs.serctl_r_is_flag_set(SerCtlR::overrun));
+            }
+        }
+        assert_eq!(*output.lock(), vec![b'>']);
+        assert!(input.lock().is_empty());
+        // the echo of the transmitted byte comes first
+        assert_eq!(received, vec![b'>', b'h', b'i', b'!']);
+    }
+
+    #[test]
+    fn console_pacing() {
+        let mut console = ComlynxConsole::new(Box::new(|| Some(0x55)), Box::new(|_| ()));
+        console.set_bit_ticks(100);
+        assert_eq!(console.receive(0).map(|p| p.tick), Some(0));
+        assert!(console.receive(1099).is_none());
+        assert_eq!(console.receive(1100).map(|p| p.tick), Some(1100));
+    }
+
+    /* The console paces its frames at the bit period Mikey derives from TIMER4, the UART of a running Lynx
+    must take them as fast as they come instead of falling behind. */
+    #[test]
+    fn lynx_pacing() {
+        let mut lynx = crate::lynx::Lynx::new();
+        let mut cart = crate::cartridge::Cartridge::default();
+        for (addr, data) in [(TIM4BKUP, 0x01), (TIM4CTLA, 0x18), (SERCTL, (SerCtlW::par_en | SerCtlW::tx_open).bits())] {
+            lynx.mikey_mut().write(addr, data, &mut cart);
+        }
+        let mut input = (0..16u8).collect::<VecDeque<u8>>();
+        let console = ComlynxConsole::new(Box::new(move || input.pop_front()), Box::new(|_| ()));
+        lynx.set_comlynx_transport(Some(Box::new(console)));
+        let capture = super::super::comlynx_capture::ComlynxCapture::new();
+        lynx.set_comlynx_capture(Some(&capture), 0);
+
+        let frame_ticks = FRAME_BITS as u64 * lynx.mikey().comlynx_bit_ticks();
+        while capture.records().len() < 16 {
+            assert!(lynx.ticks() < 32 * frame_ticks, "frames never arrived");
+            lynx.tick();
+        }
+        let records = capture.records();
+        assert_eq!(records.iter().map(|r| r.data).collect::<Vec<u8>>(), (0..16).collect::<Vec<u8>>());
+        assert!(records.windows(2).all(|r| r[1].tick - r[0].tick == frame_ticks), "{:?}", records);
+    }
+}
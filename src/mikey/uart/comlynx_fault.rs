@@ -0,0 +1,86 @@
+use alloc::{collections::VecDeque, vec::Vec};
+use super::redeye_status::RedeyeStatus;
+
+/* Alters the line level as sampled by a UART, once per received bit. `sample` is the index of the bit
+since the fault was plugged. Used to check how software recovers from a bad link. */
+pub trait ComlynxFault: Send {
+    fn sample(&mut self, sample: u64, status: RedeyeStatus) -> RedeyeStatus;
+}
+
+impl<F: FnMut(u64, RedeyeStatus) -> RedeyeStatus + Send> ComlynxFault for F {
+    fn sample(&mut self, sample: u64, status: RedeyeStatus) -> RedeyeStatus {
+        self(sample, status)
+    }
+}
+
+fn flip(status: RedeyeStatus) -> RedeyeStatus {
+    match status {
+        RedeyeStatus::High => RedeyeStatus::Low,
+        RedeyeStatus::Low => RedeyeStatus::High,
+    }
+}
+
+/* Inverts the bits sampled at the given indexes. */
+pub struct BitFlips {
+    samples: Vec<u64>,
+}
+
+impl BitFlips {
+    pub fn new(samples: &[u64]) -> Self {
+        Self { samples: samples.to_vec() }
+    }
+}
+
+impl ComlynxFault for BitFlips {
+    fn sample(&mut self, sample: u64, status: RedeyeStatus) -> RedeyeStatus {
+        match self.samples.contains(&sample) {
+            true => flip(status),
+            false => status,
+        }
+    }
+}
+
+/* Line seen `bits` bit periods late, the line is idle until then. */
+pub struct LineDelay {
+    line: VecDeque<RedeyeStatus>,
+}
+
+impl LineDelay {
+    pub fn new(bits: usize) -> Self {
+        let mut line = VecDeque::with_capacity(bits + 1);
+        line.resize(bits, RedeyeStatus::High);
+        Self { line }
+    }
+}
+
+impl ComlynxFault for LineDelay {
+    fn sample(&mut self, _sample: u64, status: RedeyeStatus) -> RedeyeStatus {
+        self.line.push_back(status);
+        self.line.pop_front().unwrap()
+    }
+}
+
+/* Flips bits at random, on average one every `one_in` bits. The seed makes runs reproducible. */
+pub struct LineNoise {
+    state: u32,
+    one_in: u32,
+}
+
+impl LineNoise {
+    pub fn new(seed: u32, one_in: u32) -> Self {
+        Self { state: seed.max(1), one_in: one_in.max(1) }
+    }
+}
+
+impl ComlynxFault for LineNoise {
+    fn sample(&mut self, _sample: u64, status: RedeyeStatus) -> RedeyeStatus {
+        // xorshift32
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 17;
+        self.state ^= self.state << 5;
+        match self.state % self.one_in {
+            0 => flip(status),
+            _ => status,
+        }
+    }
+}
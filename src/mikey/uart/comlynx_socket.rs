@@ -0,0 +1,168 @@
+use alloc::vec::Vec;
+use log::trace;
+use std::io::{ErrorKind, Read, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::Path;
+use super::comlynx_transport::{ComlynxPacket, ComlynxTransport, COMLYNX_PACKET_LEN};
+
+/* Point to point transport over a non-blocking stream, packets are sent as fixed size records. */
+pub struct ComlynxSocket<S: Read + Write + Send> {
+    stream: S,
+    rx_buffer: Vec<u8>,
+    tx_buffer: Vec<u8>,
+    connected: bool,
+}
+
+impl ComlynxSocket<TcpStream> {
+    pub fn connect_tcp<A: ToSocketAddrs>(addr: A) -> Result<Self, &'static str> {
+        match TcpStream::connect(addr) {
+            Err(_) => Err("ComLynx TCP connection failed."),
+            Ok(stream) => Self::from_tcp_stream(stream),
+        }
+    }
+
+    /* Blocks until the other end connects. */
+    pub fn accept_tcp<A: ToSocketAddrs>(addr: A) -> Result<Self, &'static str> {
+        let listener = TcpListener::bind(addr).map_err(|_| "ComLynx TCP bind failed.")?;
+        match listener.accept() {
+            Err(_) => Err("ComLynx TCP accept failed."),
+            Ok((stream, _)) => Self::from_tcp_stream(stream),
+        }
+    }
+
+    fn from_tcp_stream(stream: TcpStream) -> Result<Self, &'static str> {
+        stream.set_nodelay(true).map_err(|_| "ComLynx TCP setup failed.")?;
+        stream.set_nonblocking(true).map_err(|_| "ComLynx TCP setup failed.")?;
+        Ok(Self::new(stream))
+    }
+}
+
+#[cfg(unix)]
+impl ComlynxSocket<UnixStream> {
+    pub fn connect_unix<P: AsRef<Path>>(path: P) -> Result<Self, &'static str> {
+        match UnixStream::connect(path) {
+            Err(_) => Err("ComLynx socket connection failed."),
+            Ok(stream) => Self::from_unix_stream(stream),
+        }
+    }
+
+    /* Blocks until the other end connects. A stale socket file at `path` is replaced. */
+    pub fn accept_unix<P: AsRef<Path>>(path: P) -> Result<Self, &'static str> {
+        let _ = std::fs::remove_file(path.as_ref());
+        let listener = UnixListener::bind(path).map_err(|_| "ComLynx socket bind failed.")?;
+        match listener.accept() {
+            Err(_) => Err("ComLynx socket accept failed."),
+            Ok((stream, _)) => Self::from_unix_stream(stream),
+        }
+    }
+
+    fn from_unix_stream(stream: UnixStream) -> Result<Self, &'static str> {
+        stream.set_nonblocking(true).map_err(|_| "ComLynx socket setup failed.")?;
+        Ok(Self::new(stream))
+    }
+}
+
+impl<S: Read + Write + Send> ComlynxSocket<S> {
+    fn new(stream: S) -> Self {
+        Self {
+            stream,
+            rx_buffer: Vec::new(),
+            tx_buffer: Vec::new(),
+            connected: true,
+        }
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.connected
+    }
+
+    fn flush_tx(&mut self) {
+        while self.connected && !self.tx_buffer.is_empty() {
+            match self.stream.write(&self.tx_buffer) {
+                Ok(0) => self.disconnect(),
+                Ok(n) => { self.tx_buffer.drain(..n); },
+                Err(e) if e.kind() == ErrorKind::WouldBlock => return,
+                Err(e) if e.kind() == ErrorKind::Interrupted => (),
+                Err(_) => self.disconnect(),
+            }
+        }
+    }
+
+    fn fill_rx(&mut self) {
+        let mut buf = [0; 256];
+        while self.connected {
+            match self.stream.read(&mut buf) {
+                Ok(0) => self.disconnect(),
+                Ok(n) => self.rx_buffer.extend_from_slice(&buf[..n]),
+                Err(e) if e.kind() == ErrorKind::WouldBlock => return,
+                Err(e) if e.kind() == ErrorKind::Interrupted => (),
+                Err(_) => self.disconnect(),
+            }
+        }
+    }
+
+    fn disconnect(&mut self) {
+        trace!("ComLynx socket disconnected");
+        self.connected = false;
+    }
+}
+
+impl<S: Read + Write + Send> ComlynxTransport for ComlynxSocket<S> {
+    fn send(&mut self, packet: ComlynxPacket) {
+        self.tx_buffer.extend_from_slice(&packet.to_bytes());
+        self.flush_tx();
+    }
+
+    fn receive(&mut self, _current_tick: u64) -> Option<ComlynxPacket> {
+        self.flush_tx();
+        if self.rx_buffer.len() < COMLYNX_PACKET_LEN {
+            self.fill_rx();
+        }
+        if self.rx_buffer.len() < COMLYNX_PACKET_LEN {
+            return None;
+        }
+        let packet = ComlynxPacket::from_bytes(&self.rx_buffer[..COMLYNX_PACKET_LEN]);
+        self.rx_buffer.drain(..COMLYNX_PACKET_LEN);
+        match packet {
+            Ok(p) => Some(p),
+            Err(e) => {
+                trace!("{}", e);
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mikey::uart::comlynx_transport::ComlynxFrame;
+
+    #[test]
+    fn tcp_localhost_link() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let client = std::thread::spawn(move || ComlynxSocket::connect_tcp(addr).unwrap());
+        let (stream, _) = listener.accept().unwrap();
+        let mut server = ComlynxSocket::from_tcp_stream(stream).unwrap();
+        let mut client = client.join().unwrap();
+
+        let packets = [
+            ComlynxPacket { tick: 42, frame: ComlynxFrame::Data { data: 0xA5, parity: true } },
+            ComlynxPacket { tick: 1 << 40, frame: ComlynxFrame::BreakStart },
+        ];
+        for p in packets {
+            client.send(p);
+        }
+        let mut received = vec![];
+        while received.len() < packets.len() {
+            if let Some(p) = server.receive(0) {
+                received.push(p);
+            }
+        }
+        assert_eq!(received, packets);
+        assert!(server.is_connected());
+    }
+}
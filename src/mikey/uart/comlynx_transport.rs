@@ -0,0 +1,98 @@
+use alloc::{boxed::Box, collections::VecDeque, sync::Arc};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use super::{redeye_status::RedeyeStatus, FRAME_BITS};
+
+pub const COMLYNX_PACKET_LEN: usize = 11;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum ComlynxFrame {
+    Data { data: u8, parity: bool },
+    BreakStart,
+    BreakEnd,
+}
+
+/* A frame put on the wire, `tick` is the sender's Lynx tick when it started transmitting it. */
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct ComlynxPacket {
+    pub tick: u64,
+    pub frame: ComlynxFrame,
+}
+
+impl ComlynxPacket {
+    pub fn to_bytes(&self) -> [u8; COMLYNX_PACKET_LEN] {
+        let mut bytes = [0; COMLYNX_PACKET_LEN];
+        bytes[..8].copy_from_slice(&self.tick.to_le_bytes());
+        match self.frame {
+            ComlynxFrame::Data { data, parity } => {
+                bytes[8] = 0;
+                bytes[9] = data;
+                bytes[10] = parity as u8;
+            }
+            ComlynxFrame::BreakStart => bytes[8] = 1,
+            ComlynxFrame::BreakEnd => bytes[8] = 2,
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, &'static str> {
+        if bytes.len() < COMLYNX_PACKET_LEN {
+            return Err("ComLynx packet too short.");
+        }
+        let tick = u64::from_le_bytes(bytes[..8].try_into().unwrap());
+        let frame = match bytes[8] {
+            0 => ComlynxFrame::Data { data: bytes[9], parity: bytes[10] != 0 },
+            1 => ComlynxFrame::BreakStart,
+            2 => ComlynxFrame::BreakEnd,
+            _ => return Err("Unknown ComLynx frame."),
+        };
+        Ok(Self { tick, frame })
+    }
+}
+
+/* Line levels of a frame in transmission order: start bit, data MSB first, parity and stop bit. */
+pub fn frame_bits(data: u8, parity: RedeyeStatus) -> [RedeyeStatus; FRAME_BITS] {
+    let mut bits = [RedeyeStatus::High; FRAME_BITS];
+    bits[0] = RedeyeStatus::Low;
+    for (i, bit) in bits[1..9].iter_mut().enumerate() {
+        *bit = RedeyeStatus::from((data >> (7 - i)) & 1);
+    }
+    bits[9] = parity;
+    bits
+}
+
+/* Byte level link between consoles. Unlike `ComlynxCable` the ends don't need to tick in lockstep,
+received frames are replayed bit by bit on the local line. */
+pub trait ComlynxTransport: Send {
+    fn send(&mut self, packet: ComlynxPacket);
+    fn receive(&mut self, current_tick: u64) -> Option<ComlynxPacket>;
+    /* Called when the game reprograms the baud rate, with the duration of a bit in Lynx ticks. */
+    fn set_bit_ticks(&mut self, _bit_ticks: u64) {}
+}
+
+/* In-memory transport, `pair()` returns both ends of the link. */
+pub struct ComlynxLoopback {
+    outgoing: Arc<Mutex<VecDeque<ComlynxPacket>>>,
+    incoming: Arc<Mutex<VecDeque<ComlynxPacket>>>,
+}
+
+impl ComlynxLoopback {
+    pub fn pair() -> (Box<Self>, Box<Self>) {
+        let a = Arc::new(Mutex::new(VecDeque::new()));
+        let b = Arc::new(Mutex::new(VecDeque::new()));
+        (
+            Box::new(Self { outgoing: a.clone(), incoming: b.clone() }),
+            Box::new(Self { outgoing: b, incoming: a }),
+        )
+    }
+}
+
+impl ComlynxTransport for ComlynxLoopback {
+    fn send(&mut self, packet: ComlynxPacket) {
+        self.outgoing.lock().push_back(packet);
+    }
+
+    fn receive(&mut self, _current_tick: u64) -> Option<ComlynxPacket> {
+        self.incoming.lock().pop_front()
+    }
+}
@@ -0,0 +1,107 @@
+use alloc::collections::VecDeque;
+use log::trace;
+use crate::cartridge::bs93_header::{BS93Header, BS93_HEADER_LENGTH};
+use super::comlynx_console::ConsoleParity;
+use super::comlynx_transport::{ComlynxFrame, ComlynxPacket, ComlynxTransport};
+use super::FRAME_BITS;
+
+pub const BLL_UPLOAD_MAGIC: [u8; 2] = [0x81, b'P'];
+
+/* PC side of the BLL serial loader. The program is announced with 0x81 'P', its big endian load address
+and the big endian length of the program without its header, complemented, then the bytes after the header
+are sent, as the cart loader would put them in RAM. Bytes are sent back to back at the baud rate the running
+program set in TIMER4. */
+pub struct ComlynxUploader {
+    bytes: VecDeque<u8>,
+    parity: ConsoleParity,
+    bit_ticks: u64,
+    next_tick: u64,
+}
+
+impl ComlynxUploader {
+    pub fn new(file_content: &[u8]) -> Result<Self, &'static str> {
+        let header = BS93Header::from_slice(file_content)?;
+        let program = &file_content[BS93_HEADER_LENGTH..header.length() as usize];
+        trace!("Uploading {} bytes at 0x{:04X}", program.len(), header.load_address());
+
+        let mut bytes = VecDeque::with_capacity(program.len() + 6);
+        bytes.extend(BLL_UPLOAD_MAGIC);
+        bytes.extend(header.load_address().to_be_bytes());
+        bytes.extend((program.len() as u16 ^ 0xFFFF).to_be_bytes());
+        bytes.extend(program);
+        Ok(Self {
+            bytes,
+            parity: ConsoleParity::default(),
+            bit_ticks: 0,
+            next_tick: 0,
+        })
+    }
+
+    pub fn with_parity(mut self, parity: ConsoleParity) -> Self {
+        self.parity = parity;
+        self
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.bytes.is_empty()
+    }
+}
+
+impl ComlynxTransport for ComlynxUploader {
+    fn send(&mut self, _packet: ComlynxPacket) {}
+
+    fn receive(&mut self, current_tick: u64) -> Option<ComlynxPacket> {
+        if current_tick < self.next_tick {
+            return None;
+        }
+        let data = self.bytes.pop_front()?;
+        self.next_tick = current_tick + FRAME_BITS as u64 * self.bit_ticks;
+        Some(ComlynxPacket { tick: current_tick, frame: ComlynxFrame::Data { data, parity: self.parity.bit(data) } })
+    }
+
+    fn set_bit_ticks(&mut self, bit_ticks: u64) {
+        self.bit_ticks = bit_ticks;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::boxed::Box;
+    use super::*;
+    use super::super::*;
+
+    #[test]
+    fn upload_sequence() {
+        // BS93 executable spinning on `JMP $0200`
+        let program = [0x80, 0x08, 0x02, 0x00, 0x00, 0x0D, b'B', b'S', b'9', b'3', 0x4C, 0x00, 0x02];
+        let uploader = ComlynxUploader::new(&program).unwrap();
+        assert_eq!(uploader.remaining(), program.len() - BS93_HEADER_LENGTH + 6);
+
+        let mut uart = Uart::new();
+        let mut regs = MikeyRegisters::new();
+        uart.set_bit_ticks(8);
+        uart.set_transport(Some(Box::new(uploader)));
+        regs.set_serctl(&mut uart, SerCtlW::par_en.bits());
+
+        let mut received = vec![];
+        for tick in 0..3000 {
+            uart.tick(&mut regs, tick);
+            if regs.serctl_r_is_flag_set(SerCtlR::rx_rdy) {
+                received.push(uart.get_data(&mut regs));
+                assert!(!regs.serctl_r_is_flag_set(SerCtlR::par_err));
+            }
+        }
+        assert_eq!(received, [0x81, b'P', 0x02, 0x00, 0xFF, 0xFC, 0x4C, 0x00, 0x02]);
+    }
+
+    #[test]
+    fn invalid_files() {
+        assert!(ComlynxUploader::new(&[0; 32]).is_err());
+        // length field larger than the file
+        assert!(ComlynxUploader::new(&[0x80, 0x08, 0x02, 0x00, 0x00, 0x20, b'B', b'S', b'9', b'3', 0x4C]).is_err());
+    }
+}
@@ -1,18 +1,33 @@
 
 pub mod redeye_status;
+pub mod comlynx_transport;
+pub mod comlynx_capture;
+pub mod comlynx_console;
+pub mod comlynx_fault;
+pub mod comlynx_uploader;
+#[cfg(feature = "comlynx_socket")]
+pub mod comlynx_socket;
 
 #[cfg(not(feature = "comlynx_shared_memory"))]
 pub mod comlynx_cable_mutex;
-use alloc::vec::Vec;
+#[cfg(not(feature = "comlynx_shared_memory"))]
+pub mod comlynx_bus;
+use alloc::{boxed::Box, collections::VecDeque, vec::Vec};
 #[cfg(not(feature = "comlynx_shared_memory"))]
 use comlynx_cable_mutex::ComlynxCable;
 #[cfg(feature = "comlynx_shared_memory")]
 pub mod comlynx_cable_shared_memory;
 #[cfg(feature = "comlynx_shared_memory")]
 use comlynx_cable_shared_memory::ComlynxCable;
+use comlynx_fault::ComlynxFault;
+use comlynx_capture::{CaptureFlags, CaptureKind, CaptureRecord, ComlynxCapture};
+use comlynx_transport::{frame_bits, ComlynxFrame, ComlynxPacket, ComlynxTransport};
 use redeye_status::RedeyeStatus;
 use super::*;
 
+/* start bit, 8 data bits, parity and stop bit */
+pub const FRAME_BITS: usize = 11;
+
 macro_rules! bool_parity {
     ($bit: expr) => {
         match $bit {
@@ -27,11 +42,27 @@ pub struct Uart {
     receive_register_len: u8,
     receive_register_buffer: u8,
     receive_register: Option<u8>,
+    /* Parity bit and errors of the frame being received, SERCTL keeps the errors until the CPU clears them. */
+    receive_flags: CaptureFlags,
     break_count: u64,
     transmit_register: Vec<RedeyeStatus>,
     transmit_holding_register: Option<u8>,
     redeye_pin: ComlynxCable,
     generator_delay: u8,
+    ticks: u64,
+    bit_ticks: u64,
+    #[serde(skip)]
+    transport: Option<Box<dyn ComlynxTransport>>,
+    transport_pending: Option<ComlynxPacket>,
+    transport_line: VecDeque<RedeyeStatus>,
+    tx_break: bool,
+    transport_rx_break: bool,
+    #[serde(skip)]
+    capture: Option<(ComlynxCapture, u8)>,
+    #[serde(skip)]
+    fault: Option<Box<dyn ComlynxFault>>,
+    #[serde(skip)]
+    fault_samples: u64,
 }
 
 impl Uart {
@@ -40,30 +71,51 @@ impl Uart {
             receive_register_len: 0,
             receive_register: None,
             receive_register_buffer: 0,
-            generator_delay: 8,
+            receive_flags: CaptureFlags::empty(),
+            generator_delay: 7,
             break_count: 0,
             transmit_register: vec![],
             transmit_holding_register: None,
             redeye_pin: ComlynxCable::default(),
+            ticks: 0,
+            bit_ticks: 0,
+            transport: None,
+            transport_pending: None,
+            transport_line: VecDeque::new(),
+            tx_break: false,
+            transport_rx_break: false,
+            capture: None,
+            fault: None,
+            fault_samples: 0,
         }
     }
 
+    /* Back to the state of `new()`, the cable, transport, capture and fault stay plugged. */
     pub fn reset(&mut self) {
+        self.generator_delay = 7;
+        self.ticks = 0;
         self.receive_register_len = 0;
         self.receive_register = None;
         self.receive_register_buffer = 0;
+        self.receive_flags = CaptureFlags::empty();
         self.break_count = 0;
         self.transmit_register.clear();
         self.transmit_holding_register = None;
+        self.transport_line.clear();
+        if self.tx_break {
+            self.set_break(false);
+        }
     }
 
-    pub fn tick(&mut self, regs: &mut MikeyRegisters) -> bool /* tx or rx ready interrupt */ {
+    pub fn tick(&mut self, regs: &mut MikeyRegisters, current_tick: u64) -> bool /* tx or rx ready interrupt */ {
+        self.ticks = current_tick;
+
         /* "
         The baud rate is generated by TIMER4 according to the equation
         CLOCK4 / (TIMER4 + 1) / 8
         " */
         match self.generator_delay {
-            0 => self.generator_delay = 8,
+            0 => self.generator_delay = 7,
             _ => {
                 self.generator_delay -= 1;
                 return false;
@@ -83,60 +135,101 @@ impl Uart {
     fn tx(&mut self, regs: &mut MikeyRegisters)  {
 
         if regs.serctl_w_is_flag_set(SerCtlW::tx_brk) {
+            self.set_break(true);
             self.set_redeye_pin(RedeyeStatus::Low);
             return;    
         }
 
+        /* "
+        TXEMPTY 1 = both the transmit holding register and the transmit shift register are empty
+        " */
         if self.transmit_register.is_empty() {
-            if let Some(data) = self.transmit_holding_register.take() { 
-                trace!("Transmitting 0x{:02X}", data);
-                self.load_transmit_data(data, regs);
-                regs.serctl_r_enable_flag(SerCtlR::tx_rdy);
-                regs.serctl_r_disable_flag(SerCtlR::tx_empty);
-            }
-            if self.transmit_holding_register.is_none() {
-                regs.serctl_r_enable_flag(SerCtlR::tx_empty);
+            match self.transmit_holding_register.take() {
+                Some(data) => {
+                    trace!("Transmitting 0x{:02X}", data);
+                    self.load_transmit_data(data, regs);
+                    regs.serctl_r_enable_flag(SerCtlR::tx_rdy);
+                    regs.serctl_r_disable_flag(SerCtlR::tx_empty);
+                }
+                None => regs.serctl_r_enable_flag(SerCtlR::tx_empty),
             }
         }
 
-        if let Some(to_send) = self.transmit_register.pop() {
-            self.set_redeye_pin(to_send);
-        } 
+        /* "
+        TXOPEN 1 = open collector driver, 0 = TTL driver
+        " */
+        match self.transmit_register.pop() {
+            Some(to_send) => self.set_redeye_pin(to_send),
+            None if regs.serctl_w_is_flag_set(SerCtlW::tx_open) => self.release_redeye_pin(),
+            None => self.set_redeye_pin(RedeyeStatus::High),
+        }
     }
 
-    fn load_transmit_data(&mut self, mut data: u8, regs: &mut MikeyRegisters) {
+    fn load_transmit_data(&mut self, data: u8, regs: &mut MikeyRegisters) {
+        let parity = match regs.serctl_w_is_flag_set(SerCtlW::par_en) {
+            true => {
+                let par = bool_parity!(data.count_ones() & 1 == 1);
+                match par {
+                    RedeyeStatus::High => regs.serctl_r_enable_flag(SerCtlR::par_bit),
+                    RedeyeStatus::Low => regs.serctl_r_disable_flag(SerCtlR::par_bit),
+                }
+                par
+            },
+            false => bool_parity!(regs.serctl_w_is_flag_set(SerCtlW::par_even))
+        };
+        let flags = if parity == RedeyeStatus::High { CaptureFlags::parity_bit } else { CaptureFlags::empty() };
+        self.capture(CaptureKind::Transmit, data, flags);
+        let bits = frame_bits(data, parity);
         self.transmit_register.clear();
-        // stop bit 
-        self.transmit_register.push(RedeyeStatus::High);
-        // parity
-        self.transmit_register.push(
-            match regs.serctl_w_is_flag_set(SerCtlW::par_en) {
-                true => {
-                    let par = bool_parity!(data.count_ones() & 1 == 1);
-                    match par {
-                        RedeyeStatus::High => regs.serctl_r_enable_flag(SerCtlR::par_bit),
-                        RedeyeStatus::Low => regs.serctl_r_disable_flag(SerCtlR::par_bit),
-                    }
-                    par
-                },
-                false => bool_parity!(regs.serctl_w_is_flag_set(SerCtlW::par_even))
+        self.transmit_register.extend(bits.iter().rev());
+
+        if let Some(transport) = self.transport.as_mut() {
+            transport.send(ComlynxPacket {
+                tick: self.ticks,
+                frame: ComlynxFrame::Data { data, parity: parity == RedeyeStatus::High }
+            });
+            // the sender always hears itself on the shared wire
+            self.transport_line.extend(bits);
+        }
+    }
+
+    fn line_status(&mut self) -> RedeyeStatus {
+        let Some(transport) = self.transport.as_mut() else {
+            return self.redeye_pin.status();
+        };
+
+        while let Some(packet) = self.transport_pending.take().or_else(|| transport.receive(self.ticks)) {
+            if packet.tick > self.ticks {
+                self.transport_pending = Some(packet);
+                break;
+            }
+            match packet.frame {
+                ComlynxFrame::Data { data, parity } => self.transport_line.extend(frame_bits(data, bool_parity!(parity))),
+                ComlynxFrame::BreakStart => self.transport_rx_break = true,
+                ComlynxFrame::BreakEnd => self.transport_rx_break = false,
             }
-        );
-        // data
-        for _i in 0..8 {
-            self.transmit_register.push(if data & 0x01 != 0 {RedeyeStatus::High} else {RedeyeStatus::Low});
-            data >>= 1;
         }
-        // start bit 
-        self.transmit_register.push(RedeyeStatus::Low);
+
+        if self.tx_break || self.transport_rx_break {
+            RedeyeStatus::Low
+        } else {
+            self.transport_line.pop_front().unwrap_or(RedeyeStatus::High)
+        }
     }
 
     fn rx(&mut self, regs: &mut MikeyRegisters) {
-        let redeye_status = self.redeye_pin.status();
+        let mut redeye_status = self.line_status();
+        if let Some(fault) = self.fault.as_mut() {
+            redeye_status = fault.sample(self.fault_samples, redeye_status);
+            self.fault_samples += 1;
+        }
 
         match redeye_status {
             RedeyeStatus::Low => {
                 self.break_count += 1;
+                if self.break_count == 24 {
+                    self.capture(CaptureKind::BreakReceived, 0, CaptureFlags::empty());
+                }
                 if self.break_count >= 24 {
                     regs.serctl_r_enable_flag(SerCtlR::rx_brk);
                     return;
@@ -149,16 +242,18 @@ impl Uart {
         }
 
         match self.receive_register_len {
-            0 => if redeye_status == RedeyeStatus::Low { 
+            0 if redeye_status == RedeyeStatus::Low => {
                 self.receive_register_buffer = 0;
+                self.receive_flags = CaptureFlags::empty();
                 self.receive_register_len = 1;
-            }        
+            }
             1..=8 => {
                 self.receive_register_buffer <<= 1;
                 self.receive_register_buffer |= if redeye_status == RedeyeStatus::High { 1 } else { 0 };
                 self.receive_register_len += 1;
             }
             9 => {                 
+                self.receive_flags.set(CaptureFlags::parity_bit, redeye_status == RedeyeStatus::High);
                 match regs.serctl_w_is_flag_set(SerCtlW::par_en) {
                     true => {
                         let par = bool_parity!(self.receive_register_buffer.count_ones() & 1 == 1);
@@ -170,11 +265,13 @@ impl Uart {
                         if par != redeye_status {
                             trace!("Parity Error");
                             regs.serctl_r_enable_flag(SerCtlR::par_err);
+                            self.receive_flags.insert(CaptureFlags::parity_err);
                         }
                     }
                     false => if redeye_status != bool_parity!(regs.serctl_w_is_flag_set(SerCtlW::par_even)) {
                         trace!("Parity Error");
                         regs.serctl_r_enable_flag(SerCtlR::par_err);
+                        self.receive_flags.insert(CaptureFlags::parity_err);
                     }
                 }                 
                 self.receive_register_len += 1;
@@ -183,15 +280,18 @@ impl Uart {
                 if self.receive_register_buffer != 0 && redeye_status == RedeyeStatus::Low { 
                     trace!("Frame Error");
                     regs.serctl_r_enable_flag(SerCtlR::frame_err);
+                    self.receive_flags.insert(CaptureFlags::frame_err);
                 } 
                 trace!("Received 0x{:02X}", self.receive_register_buffer); 
                 if self.receive_register.is_some() {
                     trace!("Overrun");  
                     regs.serctl_r_enable_flag(SerCtlR::overrun);
+                    self.receive_flags.insert(CaptureFlags::overrun);
                 } else {
                     self.receive_register = Some(self.receive_register_buffer);
                     regs.serctl_r_enable_flag(SerCtlR::rx_rdy);                                        
                 }
+                self.capture(CaptureKind::Receive, self.receive_register_buffer, self.receive_flags);
                 self.receive_register_len = 0;
             }
             _ => (),
@@ -217,10 +317,82 @@ impl Uart {
     }
 
     pub fn set_redeye_pin(&mut self, status: RedeyeStatus) {
-        self.redeye_pin.set(status);
+        if self.transport.is_none() {
+            self.redeye_pin.set(status);
+        }
+    }
+
+    pub fn release_redeye_pin(&mut self) {
+        if self.transport.is_none() {
+            self.redeye_pin.release();
+        }
+    }
+
+    pub fn set_break(&mut self, active: bool) {
+        if self.tx_break == active {
+            return;
+        }
+        self.tx_break = active;
+        self.capture(if active { CaptureKind::BreakStart } else { CaptureKind::BreakEnd }, 0, CaptureFlags::empty());
+
+        match self.transport.as_mut() {
+            None => if active {
+                self.redeye_pin.set(RedeyeStatus::Low);
+            } else {
+                self.redeye_pin.set(RedeyeStatus::High);
+                self.redeye_pin.release();
+            },
+            Some(transport) => transport.send(ComlynxPacket {
+                tick: self.ticks,
+                frame: if active { ComlynxFrame::BreakStart } else { ComlynxFrame::BreakEnd }
+            }),
+        }
+    }
+
+    /* Logs the traffic of this unit into `capture` under the `sender` id, `None` stops capturing. */
+    pub fn set_capture(&mut self, capture: Option<&ComlynxCapture>, sender: u8) {
+        self.capture = capture.map(|c| (c.clone(), sender));
+    }
+
+    fn capture(&self, kind: CaptureKind, data: u8, flags: CaptureFlags) {
+        if let Some((capture, sender)) = &self.capture {
+            capture.push(CaptureRecord { tick: self.ticks, sender: *sender, kind, data, flags });
+        }
+    }
+
+    /* Plugs a fault on the receiving side of this unit, `None` restores a clean line. */
+    pub fn set_fault(&mut self, fault: Option<Box<dyn ComlynxFault>>) {
+        self.fault = fault;
+        self.fault_samples = 0;
+    }
+
+    /* Replaces the wire level cable by a byte level transport, `None` plugs the cable back. */
+    pub fn set_transport(&mut self, mut transport: Option<Box<dyn ComlynxTransport>>) {
+        if let Some(t) = transport.as_mut() {
+            t.set_bit_ticks(self.bit_ticks);
+        }
+        self.transport = transport;
+        self.transport_pending = None;
+        self.transport_line.clear();
+        self.tx_break = false;
+        self.transport_rx_break = false;
+    }
+
+    pub fn bit_ticks(&self) -> u64 {
+        self.bit_ticks
+    }
+
+    pub fn set_bit_ticks(&mut self, bit_ticks: u64) {
+        self.bit_ticks = bit_ticks;
+        if let Some(t) = self.transport.as_mut() {
+            t.set_bit_ticks(bit_ticks);
+        }
+    }
+
+    pub fn has_transport(&self) -> bool {
+        self.transport.is_some()
     }
 
-    #[cfg(not(feature = "comlynx_shared_memory"))]
     pub fn set_cable(&mut self, cable: &ComlynxCable) {
         self.redeye_pin = cable.clone();
     }
@@ -228,10 +400,472 @@ impl Uart {
     pub fn cable(&self) -> &ComlynxCable {
         &self.redeye_pin
     }
+
+    /* Moves the links of `other` to the outside world, its cable, transport with the frames in flight, capture
+    and fault, to this unit. `other` is left detached. */
+    pub(crate) fn take_links(&mut self, other: &mut Uart) {
+        let detached = other.redeye_pin.detached();
+        self.redeye_pin = core::mem::replace(&mut other.redeye_pin, detached);
+        self.transport = other.transport.take();
+        self.transport_pending = other.transport_pending.take();
+        self.transport_line = core::mem::take(&mut other.transport_line);
+        self.transport_rx_break = core::mem::take(&mut other.transport_rx_break);
+        self.capture = other.capture.take();
+        self.fault = other.fault.take();
+        self.fault_samples = other.fault_samples;
+    }
+}
+
+/* A clone is detached: it gets a cable of its own at the current level, no transport, capture or fault. */
+impl Clone for Uart {
+    fn clone(&self) -> Self {
+        Self {
+            receive_register_len: self.receive_register_len,
+            receive_register_buffer: self.receive_register_buffer,
+            receive_register: self.receive_register,
+            receive_flags: self.receive_flags,
+            break_count: self.break_count,
+            transmit_register: self.transmit_register.clone(),
+            transmit_holding_register: self.transmit_holding_register,
+            redeye_pin: self.redeye_pin.detached(),
+            generator_delay: self.generator_delay,
+            ticks: self.ticks,
+            bit_ticks: self.bit_ticks,
+            transport: None,
+            transport_pending: None,
+            transport_line: VecDeque::new(),
+            tx_break: self.tx_break,
+            transport_rx_break: false,
+            capture: None,
+            fault: None,
+            fault_samples: 0,
+        }
+    }
 }
 
 impl Default for Uart {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use comlynx_transport::ComlynxLoopback;
+
+    struct Port {
+        uart: Uart,
+        regs: MikeyRegisters,
+        received: Vec<u8>,
+    }
+
+    impl Port {
+        fn new(transport: Box<dyn ComlynxTransport>) -> Self {
+            let mut port = Self { uart: Uart::new(), regs: MikeyRegisters::new(), received: vec![] };
+            port.uart.set_transport(Some(transport));
+            port.regs.set_serctl(&mut port.uart, (SerCtlW::par_en | SerCtlW::tx_open).bits());
+            port
+        }
+
+        fn tick(&mut self, tick: u64) {
+            self.uart.tick(&mut self.regs, tick);
+            if self.regs.serctl_r_is_flag_set(SerCtlR::rx_rdy) {
+                let data = self.uart.get_data(&mut self.regs);
+                self.received.push(data);
+            }
+        }
+    }
+
+    #[test]
+    fn transport_data_and_echo() {
+        let (a, b) = ComlynxLoopback::pair();
+        let mut port_a = Port::new(a);
+        let mut port_b = Port::new(b);
+
+        port_a.uart.set_transmit_holding_buffer(&mut port_a.regs, 0xC3);
+        for tick in 0..1000 {
+            port_a.tick(tick);
+            // the receiving end runs 100 ticks late, frames are replayed on its own timeline
+            port_b.tick(tick.saturating_sub(100));
+        }
+        assert_eq!(port_a.received, vec![0xC3]);
+        assert_eq!(port_b.received, vec![0xC3]);
+        assert!(!port_b.regs.serctl_r_is_flag_set(SerCtlR::par_err));
+        assert!(!port_b.regs.serctl_r_is_flag_set(SerCtlR::frame_err));
+    }
+
+    #[test]
+    fn transport_break() {
+        let (a, b) = ComlynxLoopback::pair();
+        let mut port_a = Port::new(a);
+        let mut port_b = Port::new(b);
+
+        port_a.regs.set_serctl(&mut port_a.uart, (SerCtlW::par_en | SerCtlW::tx_open | SerCtlW::tx_brk).bits());
+        for tick in 0..300 {
+            port_a.tick(tick);
+            port_b.tick(tick);
+        }
+        assert!(port_b.regs.serctl_r_is_flag_set(SerCtlR::rx_brk));
+
+        port_a.regs.set_serctl(&mut port_a.uart, (SerCtlW::par_en | SerCtlW::tx_open).bits());
+        for tick in 300..320 {
+            port_a.tick(tick);
+            port_b.tick(tick);
+        }
+        assert!(!port_b.regs.serctl_r_is_flag_set(SerCtlR::rx_brk));
+    }
+
+    #[test]
+    fn packet_encoding() {
+        let packets = [
+            ComlynxPacket { tick: 0x0123_4567_89AB_CDEF, frame: ComlynxFrame::Data { data: 0x81, parity: false } },
+            ComlynxPacket { tick: 7, frame: ComlynxFrame::BreakStart },
+            ComlynxPacket { tick: 8, frame: ComlynxFrame::BreakEnd },
+        ];
+        for p in packets {
+            assert_eq!(ComlynxPacket::from_bytes(&p.to_bytes()), Ok(p));
+        }
+        assert!(ComlynxPacket::from_bytes(&[0; 10]).is_err());
+    }
+
+    #[test]
+    fn capture_and_replay() {
+        let (a, b) = ComlynxLoopback::pair();
+        let mut port_a = Port::new(a);
+        let mut port_b = Port::new(b);
+        let capture = ComlynxCapture::new();
+        port_a.uart.set_capture(Some(&capture), 1);
+        port_b.uart.set_capture(Some(&capture), 2);
+
+        port_a.uart.set_transmit_holding_buffer(&mut port_a.regs, 0x42);
+        for tick in 0..200 {
+            port_a.tick(tick);
+            port_b.tick(tick);
+        }
+        port_b.regs.set_serctl(&mut port_b.uart, (SerCtlW::par_en | SerCtlW::tx_open | SerCtlW::tx_brk).bits());
+        for tick in 200..500 {
+            port_a.tick(tick);
+            port_b.tick(tick);
+        }
+
+        let records = capture.records();
+        let kinds: Vec<(u8, CaptureKind)> = records.iter().map(|r| (r.sender, r.kind)).collect();
+        assert_eq!(kinds[..4], [
+            (1, CaptureKind::Transmit),
+            (1, CaptureKind::Receive),
+            (2, CaptureKind::Receive),
+            (2, CaptureKind::BreakStart),
+        ]);
+        // a break first reads as 0x00 bytes until it lasts long enough
+        assert!(records[4..].iter().filter(|r| r.kind == CaptureKind::Receive).all(|r| r.data == 0));
+        assert!(kinds.contains(&(1, CaptureKind::BreakReceived)));
+        assert!(kinds.contains(&(2, CaptureKind::BreakReceived)));
+        assert!(records[..3].iter().all(|r| r.data == 0x42 && r.flags == CaptureFlags::empty()));
+
+        let pcap = capture.to_pcap();
+        assert_eq!(ComlynxCapture::from_pcap(&pcap), Ok(records.clone()));
+        assert!(ComlynxCapture::from_pcap(&pcap[1..]).is_err());
+
+        // unit 2 alone, fed with unit 1 traffic
+        let mut replayed = Port::new(alloc::boxed::Box::new(comlynx_capture::ComlynxReplay::new(&records, 2)));
+        for tick in 0..200 {
+            replayed.tick(tick);
+        }
+        assert_eq!(replayed.received, vec![0x42]);
+    }
+
+    #[test]
+    fn parity_modes() {
+        for mode in [SerCtlW::par_en, SerCtlW::par_en | SerCtlW::par_even, SerCtlW::par_even, SerCtlW::empty()] {
+            let (a, b) = ComlynxLoopback::pair();
+            let mut port_a = Port::new(a);
+            let mut port_b = Port::new(b);
+            port_a.regs.set_serctl(&mut port_a.uart, (mode | SerCtlW::tx_open).bits());
+            port_b.regs.set_serctl(&mut port_b.uart, (mode | SerCtlW::tx_open).bits());
+
+            for data in [0x00, 0x01, 0x7F, 0xFF] {
+                port_a.uart.set_transmit_holding_buffer(&mut port_a.regs, data);
+                for tick in 0..200 {
+                    port_a.tick(tick);
+                    port_b.tick(tick);
+                }
+                assert_eq!(port_b.received.pop(), Some(data));
+                assert!(!port_b.regs.serctl_r_is_flag_set(SerCtlR::par_err), "mode {:?} data {:02X}", mode, data);
+                if mode.contains(SerCtlW::par_en) {
+                    // PAREVEN is ignored when parity is enabled, the parity bit is always computed
+                    assert_eq!(port_b.regs.serctl_r_is_flag_set(SerCtlR::par_bit), data.count_ones() & 1 == 1);
+                }
+            }
+        }
+
+        // the receiver expects the PAREVEN level when parity is disabled
+        let (a, b) = ComlynxLoopback::pair();
+        let mut port_a = Port::new(a);
+        let mut port_b = Port::new(b);
+        port_a.regs.set_serctl(&mut port_a.uart, (SerCtlW::par_even | SerCtlW::tx_open).bits());
+        port_b.regs.set_serctl(&mut port_b.uart, SerCtlW::tx_open.bits());
+        port_a.uart.set_transmit_holding_buffer(&mut port_a.regs, 0x55);
+        for tick in 0..200 {
+            port_a.tick(tick);
+            port_b.tick(tick);
+        }
+        assert_eq!(port_b.received, vec![0x55]);
+        assert!(port_b.regs.serctl_r_is_flag_set(SerCtlR::par_err));
+    }
+
+    #[test]
+    fn noisy_line_is_reproducible() {
+        let run = || {
+            let (a, b) = ComlynxLoopback::pair();
+            let mut port_a = Port::new(a);
+            let mut port_b = Port::new(b);
+            port_b.uart.set_fault(Some(Box::new(comlynx_fault::LineNoise::new(1234, 40))));
+            let mut errors = 0;
+            for (i, data) in (0..32u8).enumerate() {
+                port_a.uart.set_transmit_holding_buffer(&mut port_a.regs, data);
+                for tick in i as u64 * 200..(i as u64 + 1) * 200 {
+                    port_a.tick(tick);
+                    port_b.tick(tick);
+                }
+                if port_b.regs.serctl_r_is_flag_set(SerCtlR::par_err) || port_b.regs.serctl_r_is_flag_set(SerCtlR::frame_err) {
+                    errors += 1;
+                    port_b.regs.set_serctl(&mut port_b.uart, (SerCtlW::par_en | SerCtlW::tx_open | SerCtlW::reset_err).bits());
+                }
+            }
+            (port_b.received, errors)
+        };
+        let (received, errors) = run();
+        assert_eq!(run(), (received.clone(), errors));
+        assert!(errors > 0);
+        assert_ne!(received, (0..32u8).collect::<Vec<u8>>());
+    }
+
+    /* Receiving unit on a point to point cable driven by the test, one level per bit period. */
+    #[cfg(not(feature = "comlynx_shared_memory"))]
+    mod line {
+        use super::*;
+
+        struct Line {
+            uart: Uart,
+            regs: MikeyRegisters,
+            cable: ComlynxCable,
+            ticks: u64,
+        }
+
+        impl Line {
+            fn new(serctl: SerCtlW) -> Self {
+                let mut line = Self { uart: Uart::new(), regs: MikeyRegisters::new(), cable: ComlynxCable::default(), ticks: 0 };
+                line.uart.set_cable(&line.cable);
+                // open collector, the receiver doesn't hold the line while idle
+                line.regs.set_serctl(&mut line.uart, (serctl | SerCtlW::tx_open).bits());
+                line
+            }
+
+            /* Returns true if the UART requested an interrupt during the bit. */
+            fn bit(&mut self, status: RedeyeStatus) -> bool {
+                self.cable.set(status);
+                let mut irq = false;
+                for _ in 0..8 {
+                    irq |= self.uart.tick(&mut self.regs, self.ticks);
+                    self.ticks += 1;
+                }
+                irq
+            }
+
+            fn frame(&mut self, data: u8, parity: RedeyeStatus, stop: RedeyeStatus) {
+                let mut bits = frame_bits(data, parity);
+                bits[FRAME_BITS - 1] = stop;
+                for b in bits {
+                    self.bit(b);
+                }
+            }
+
+            fn flag(&self, flag: SerCtlR) -> bool {
+                self.regs.serctl_r_is_flag_set(flag)
+            }
+        }
+
+        use comlynx_fault::{BitFlips, LineDelay};
+
+        #[test]
+        fn bit_period() {
+            // "CLOCK4 / (TIMER4 + 1) / 8", 0xAA with a high parity bit changes level at every bit
+            let mut line = Line::new(SerCtlW::par_even);
+            line.uart.set_transmit_holding_buffer(&mut line.regs, 0xAA);
+            let mut level = line.cable.status();
+            let mut edges = vec![];
+            for tick in 0..200 {
+                line.uart.tick(&mut line.regs, tick);
+                if line.cable.status() != level {
+                    level = line.cable.status();
+                    edges.push(tick);
+                }
+            }
+            assert_eq!(edges.len(), FRAME_BITS - 1);
+            assert!(edges.windows(2).all(|w| w[1] - w[0] == 8), "{:?}", edges);
+        }
+
+        #[test]
+        fn parity_errors() {
+            // parity enabled: odd number of ones gives a high parity bit
+            let mut line = Line::new(SerCtlW::par_en);
+            line.frame(0x01, RedeyeStatus::High, RedeyeStatus::High);
+            assert!(line.flag(SerCtlR::rx_rdy) && !line.flag(SerCtlR::par_err));
+            assert_eq!(line.uart.get_data(&mut line.regs), 0x01);
+            line.frame(0x03, RedeyeStatus::High, RedeyeStatus::High);
+            assert!(line.flag(SerCtlR::par_err));
+
+            // parity disabled: the parity bit must match PAREVEN
+            for (par_even, level) in [(SerCtlW::par_even, RedeyeStatus::High), (SerCtlW::empty(), RedeyeStatus::Low)] {
+                let mut line = Line::new(par_even);
+                line.frame(0x01, level, RedeyeStatus::High);
+                assert!(!line.flag(SerCtlR::par_err));
+                line.uart.get_data(&mut line.regs);
+                line.frame(0x01, if level == RedeyeStatus::High { RedeyeStatus::Low } else { RedeyeStatus::High }, RedeyeStatus::High);
+                assert!(line.flag(SerCtlR::par_err));
+            }
+        }
+
+        #[test]
+        fn capture_flags_per_frame() {
+            let mut line = Line::new(SerCtlW::par_en);
+            let capture = ComlynxCapture::new();
+            line.uart.set_capture(Some(&capture), 1);
+            line.frame(0x03, RedeyeStatus::High, RedeyeStatus::Low);
+            line.uart.get_data(&mut line.regs);
+            line.frame(0x01, RedeyeStatus::High, RedeyeStatus::High);
+            line.frame(0x02, RedeyeStatus::High, RedeyeStatus::High);
+            // SERCTL still reports the errors of the first frame
+            assert!(line.flag(SerCtlR::par_err) && line.flag(SerCtlR::frame_err) && line.flag(SerCtlR::overrun));
+            let flags: Vec<(u8, CaptureFlags)> = capture.records().iter().map(|r| (r.data, r.flags)).collect();
+            assert_eq!(flags, [
+                (0x03, CaptureFlags::parity_bit | CaptureFlags::parity_err | CaptureFlags::frame_err),
+                (0x01, CaptureFlags::parity_bit),
+                (0x02, CaptureFlags::parity_bit | CaptureFlags::overrun),
+            ]);
+        }
+
+        #[test]
+        fn overrun() {
+            let mut line = Line::new(SerCtlW::par_en);
+            line.frame(0x11, RedeyeStatus::Low, RedeyeStatus::High);
+            line.frame(0x22, RedeyeStatus::Low, RedeyeStatus::High);
+            assert!(line.flag(SerCtlR::overrun));
+            // the first byte is kept
+            assert_eq!(line.uart.get_data(&mut line.regs), 0x11);
+            assert!(!line.flag(SerCtlR::rx_rdy));
+
+            line.regs.set_serctl(&mut line.uart, (SerCtlW::par_en | SerCtlW::tx_open | SerCtlW::reset_err).bits());
+            assert!(!line.flag(SerCtlR::overrun));
+        }
+
+        #[test]
+        fn framing_error() {
+            let mut line = Line::new(SerCtlW::par_en);
+            line.frame(0x80, RedeyeStatus::High, RedeyeStatus::Low);
+            assert!(line.flag(SerCtlR::frame_err));
+            assert!(line.flag(SerCtlR::rx_rdy));
+            assert_eq!(line.uart.get_data(&mut line.regs), 0x80);
+        }
+
+        #[test]
+        fn break_detection() {
+            let mut line = Line::new(SerCtlW::par_en);
+            for _ in 0..23 {
+                line.bit(RedeyeStatus::Low);
+            }
+            assert!(!line.flag(SerCtlR::rx_brk));
+            // the start of a break reads as a 0x00 byte without framing error
+            assert_eq!(line.uart.get_data(&mut line.regs), 0x00);
+            assert!(!line.flag(SerCtlR::frame_err));
+
+            line.bit(RedeyeStatus::Low);
+            assert!(line.flag(SerCtlR::rx_brk));
+            line.bit(RedeyeStatus::Low);
+            assert!(line.flag(SerCtlR::rx_brk));
+            line.bit(RedeyeStatus::High);
+            assert!(!line.flag(SerCtlR::rx_brk));
+        }
+
+        #[test]
+        fn level_interrupts() {
+            let mut line = Line::new(SerCtlW::rx_int_en | SerCtlW::par_en);
+            line.frame(0x42, RedeyeStatus::Low, RedeyeStatus::High);
+            // stays asserted as long as the byte is not read
+            assert!(line.bit(RedeyeStatus::High));
+            assert!(line.bit(RedeyeStatus::High));
+            line.uart.get_data(&mut line.regs);
+            assert!(!line.bit(RedeyeStatus::High));
+
+            let mut line = Line::new(SerCtlW::tx_int_en);
+            assert!(line.bit(RedeyeStatus::High));
+            assert!(line.bit(RedeyeStatus::High));
+            line.uart.set_transmit_holding_buffer(&mut line.regs, 0x42);
+            assert!(!line.flag(SerCtlR::tx_rdy));
+            // the holding register is moved to the shift register at the next bit, ready again
+            assert!(line.bit(RedeyeStatus::High));
+            assert!(!line.flag(SerCtlR::tx_empty));
+            for _ in 0..FRAME_BITS {
+                line.bit(RedeyeStatus::High);
+            }
+            assert!(line.flag(SerCtlR::tx_empty));
+
+            line.regs.set_serctl(&mut line.uart, SerCtlW::tx_open.bits());
+            assert!(!line.bit(RedeyeStatus::High));
+        }
+
+        #[test]
+        fn tx_open_driver() {
+            for (serctl, collisions) in [(SerCtlW::tx_open, 0), (SerCtlW::empty(), 1)] {
+                let bus = super::super::comlynx_bus::ComlynxBus::new();
+                let mut units: Vec<(Uart, MikeyRegisters)> = [SerCtlW::tx_open, serctl].into_iter().map(|ctl| {
+                    let mut uart = Uart::new();
+                    let mut regs = MikeyRegisters::new();
+                    uart.set_cable(&bus.attach().unwrap());
+                    regs.set_serctl(&mut uart, ctl.bits());
+                    (uart, regs)
+                }).collect();
+
+                // the idle unit with a TTL driver holds the line high against the sender
+                let (uart, regs) = &mut units[0];
+                uart.set_transmit_holding_buffer(regs, 0x00);
+                for tick in 0..200 {
+                    for (uart, regs) in units.iter_mut() {
+                        uart.tick(regs, tick);
+                    }
+                }
+                assert_eq!(bus.collisions(), collisions);
+                let (uart, regs) = &mut units[1];
+                assert_eq!(uart.get_data(regs), 0x00);
+            }
+        }
+
+        #[test]
+        fn bit_flip_and_delay() {
+            let mut line = Line::new(SerCtlW::par_en);
+            // sample 8 is the last data bit of a frame starting at sample 0
+            line.uart.set_fault(Some(Box::new(BitFlips::new(&[8]))));
+            line.frame(0x00, RedeyeStatus::Low, RedeyeStatus::High);
+            assert_eq!(line.uart.get_data(&mut line.regs), 0x01);
+            assert!(line.flag(SerCtlR::par_err));
+
+            let mut line = Line::new(SerCtlW::par_en);
+            line.uart.set_fault(Some(Box::new(LineDelay::new(3))));
+            line.frame(0x5A, RedeyeStatus::Low, RedeyeStatus::High);
+            assert!(!line.flag(SerCtlR::rx_rdy));
+            for _ in 0..3 {
+                line.bit(RedeyeStatus::High);
+            }
+            assert!(line.flag(SerCtlR::rx_rdy));
+            assert_eq!(line.uart.get_data(&mut line.regs), 0x5A);
+
+            // faults can also be closures
+            let mut line = Line::new(SerCtlW::par_en);
+            line.uart.set_fault(Some(Box::new(|_, _| RedeyeStatus::Low)));
+            for _ in 0..24 {
+                line.bit(RedeyeStatus::High);
+            }
+            assert!(line.flag(SerCtlR::rx_brk));
+        }
+    }
+}
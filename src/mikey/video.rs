@@ -11,6 +11,65 @@ pub const RGB_SCREEN_BUFFER_LEN: usize = SCREEN_BUFFER_LEN * 3;
 pub const RGBA_SCREEN_BUFFER_LEN: usize = SCREEN_BUFFER_LEN * 4;
 const VBLANK_HSYNC_COUNT: u16 = 3;
 
+// Approximation of the original LCD response: raised black level and a steep gamma curve.
+const LCD_GAMMA_LEVELS: [u8; 16] = [0x18, 0x1B, 0x20, 0x28, 0x31, 0x3C, 0x48, 0x55, 0x64, 0x74, 0x85, 0x97, 0xAA, 0xBD, 0xD2, 0xE8];
+
+#[derive(Clone, Copy, Default, PartialEq, Debug, Serialize, Deserialize)]
+pub enum ColorProfile {
+    #[default]
+    Raw,
+    LcdGamma,
+    Vivid,
+}
+
+impl ColorProfile {
+    pub fn rgb(&self, red: u8, green: u8, blue: u8) -> [u8; 3] {
+        match self {
+            ColorProfile::Raw => [red * 16, green * 16, blue * 16],
+            ColorProfile::LcdGamma => [LCD_GAMMA_LEVELS[red as usize], LCD_GAMMA_LEVELS[green as usize], LCD_GAMMA_LEVELS[blue as usize]],
+            ColorProfile::Vivid => {
+                let (r, g, b) = (red as i32 * 17, green as i32 * 17, blue as i32 * 17);
+                let luma = (r * 77 + g * 150 + b * 29) >> 8;
+                let saturate = |c: i32| (luma + (c - luma) * 5 / 4).clamp(0, 255) as u8;
+                [saturate(r), saturate(g), saturate(b)]
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct VideoTiming {
+    pub frame_rate: f64,
+    pub line_count: u16,
+    pub visible_lines: u16,
+    pub ticks_per_line: u32,
+    pub ticks_per_frame: u32,
+    pub pbkup: u8,
+}
+
+impl VideoTiming {
+    /* Timer 0 is the horizontal line timer, timer 2 counts the lines and is clocked by timer 0. */
+    pub fn new(tim0_ctla: u8, tim0_bkup: u8, tim2_bkup: u8, pbkup: u8) -> Self {
+        let line_count = tim2_bkup as u16 + 1;
+        let ticks_per_line = match tim0_ctla & 0b111 {
+            7 => 0, // linked, timer 0 has no source
+            period => (tim0_bkup as u32 + 1) * TIMER_TICKS_COUNT as u32 * u32::pow(2, period as u32),
+        };
+        let ticks_per_frame = ticks_per_line * line_count as u32;
+        Self {
+            frame_rate: match ticks_per_frame {
+                0 => 0.,
+                t => CRYSTAL_FREQ as f64 / t as f64,
+            },
+            line_count,
+            visible_lines: line_count.saturating_sub(VBLANK_HSYNC_COUNT).min(LYNX_SCREEN_HEIGHT as u16),
+            ticks_per_line,
+            ticks_per_frame,
+            pbkup,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 struct VideoBuffer {
     #[serde(skip)]
@@ -27,7 +86,7 @@ fn create_rgb_buffer() -> Vec<u8> {
     vec![0; RGB_SCREEN_BUFFER_LEN]
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Video {
     #[serde(skip)]
     #[serde[default="create_video_buffers"]]
@@ -36,6 +95,9 @@ pub struct Video {
     pix_buffer: u64,
     pix_buffer_available: u8,
     redraw_requested: bool,
+    frame_count: u64,
+    #[serde(skip)]
+    ghosting: u8,
 }
 
 fn create_video_buffers() -> Vec<VideoBuffer> {
@@ -119,6 +181,8 @@ impl Video {
             pix_buffer: 0,
             pix_buffer_available: 0,
             redraw_requested: false,
+            frame_count: 0,
+            ghosting: 0,
         }
     }
 
@@ -139,6 +203,14 @@ impl Video {
         }
     }
 
+    pub fn frame_count(&self) -> u64 {
+        self.frame_count
+    }
+
+    pub fn line(&self) -> u16 {
+        self.buffers[self.draw_buffer].hsync_count
+    }
+
     pub fn push_pix_buffer(&mut self, pixs: &[u8]) {
         self.pix_buffer = 
             pixel!(pixs[0]) | (pixel!(pixs[1]) << 8)  | (pixel!(pixs[2]) << 16) | (pixel!(pixs[3]) << 24) |
@@ -159,11 +231,38 @@ impl Video {
         self.buffers[1].set_pbkup(value);
     }
 
+    pub fn ghosting(&self) -> u8 {
+        self.ghosting
+    }
+
+    /* Weight (out of 256) of the previous frame blended into each new frame, 0 disables it.
+    Emulates the LCD persistence some games rely on for flicker transparency. */
+    pub fn set_ghosting(&mut self, ghosting: u8) {
+        self.ghosting = ghosting;
+    }
+
+    fn blend_previous_frame(&mut self) {
+        let prev_weight = self.ghosting as u16;
+        let curr_weight = 256 - prev_weight;
+        let (first, second) = self.buffers.split_at_mut(1);
+        let (curr, prev) = match self.draw_buffer {
+            0 => (&mut first[0], &second[0]),
+            _ => (&mut second[0], &first[0]),
+        };
+        for (c, p) in curr.rgb_buffer.iter_mut().zip(prev.rgb_buffer.iter()) {
+            *c = ((*c as u16 * curr_weight + *p as u16 * prev_weight) >> 8) as u8;
+        }
+    }
+
     pub fn vsync(&mut self) {
+        if self.ghosting != 0 {
+            self.blend_previous_frame();
+        }
         self.swap_buffers();
         self.draw_buffer().reset();
         self.pix_buffer_available = 0;
         self.redraw_requested = true;
+        self.frame_count += 1;
     }  
 
     pub fn hsync(&mut self) {
@@ -203,4 +302,32 @@ impl Default for Video {
     fn default() -> Self {
         Self::new()
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn video_timing_60hz() {
+        let t = VideoTiming::new(0x18, 0x9E, 0x68, 0x29);
+        assert_eq!(t.line_count, 105);
+        assert_eq!(t.visible_lines, 102);
+        assert_eq!(t.ticks_per_line, 159 * 16);
+        assert_eq!(t.ticks_per_frame, 159 * 16 * 105);
+        assert!((t.frame_rate - 59.89).abs() < 0.01);
+    }
+
+    #[test]
+    fn video_timing_50hz_75hz() {
+        assert!((VideoTiming::new(0x18, 0xBD, 0x68, 0).frame_rate - 50.13).abs() < 0.01);
+        assert!((VideoTiming::new(0x18, 0x7E, 0x68, 0).frame_rate - 74.99).abs() < 0.01);
+    }
+
+    #[test]
+    fn video_timing_prescaler() {
+        let t = VideoTiming::new(0x19, 0x4E, 0x68, 0);
+        assert_eq!(t.ticks_per_line, 79 * 32);
+        assert_eq!(VideoTiming::new(0x1F, 0x9E, 0x68, 0).frame_rate, 0.);
+    }
 }
\ No newline at end of file
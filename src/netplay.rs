@@ -0,0 +1,360 @@
+use alloc::{collections::VecDeque, sync::Arc, vec::Vec};
+use log::trace;
+use parking_lot::Mutex;
+use serde::{Serialize, Deserialize};
+use crate::input::{InputLatch, InputOrientation, LynxInput};
+use crate::lynx::{Lynx, Snapshot};
+use crate::mikey::uart::{comlynx_cable_mutex::ComlynxCable, redeye_status::RedeyeStatus};
+
+pub const PLAYERS: usize = 2;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct InputPacket {
+    pub frame: u64,
+    pub input: LynxInput,
+}
+
+pub trait NetplayTransport {
+    fn send(&mut self, packet: InputPacket);
+    fn receive(&mut self) -> Option<InputPacket>;
+}
+
+/* In-memory transport, `pair()` returns both ends of the link. */
+pub struct LoopbackTransport {
+    outgoing: Arc<Mutex<VecDeque<InputPacket>>>,
+    incoming: Arc<Mutex<VecDeque<InputPacket>>>,
+}
+
+impl LoopbackTransport {
+    pub fn pair() -> (Self, Self) {
+        let a = Arc::new(Mutex::new(VecDeque::new()));
+        let b = Arc::new(Mutex::new(VecDeque::new()));
+        (
+            Self { outgoing: a.clone(), incoming: b.clone() },
+            Self { outgoing: b, incoming: a },
+        )
+    }
+}
+
+impl NetplayTransport for LoopbackTransport {
+    fn send(&mut self, packet: InputPacket) {
+        self.outgoing.lock().push_back(packet);
+    }
+
+    fn receive(&mut self) -> Option<InputPacket> {
+        self.incoming.lock().pop_front()
+    }
+}
+
+struct FrameSnapshot {
+    frame: u64,
+    consoles: [Snapshot; PLAYERS],
+    line: RedeyeStatus,
+}
+
+/* Every peer emulates both consoles, linked by an in-process cable, and only inputs are exchanged.
+Remote inputs not received yet are predicted by repeating the last known one. When the actual input
+differs from the prediction, the consoles are restored to the snapshot of that frame and resimulated.
+Inputs are only kept as far back as the oldest snapshot, an input older than that can't be checked anymore. */
+pub struct RollbackSession<T: NetplayTransport> {
+    consoles: [Lynx; PLAYERS],
+    cable: ComlynxCable,
+    local_player: usize,
+    transport: T,
+    frame: u64,
+    /* Frame of the first entry of `confirmed_inputs` and `used_inputs`. */
+    first_frame: u64,
+    /* Last input confirmed before `first_frame`, the prediction for the frames following it. */
+    known_inputs: [LynxInput; PLAYERS],
+    confirmed_inputs: [Vec<Option<LynxInput>>; PLAYERS],
+    used_inputs: Vec<[LynxInput; PLAYERS]>,
+    snapshots: VecDeque<FrameSnapshot>,
+    max_rollback: usize,
+    rollbacks: u64,
+}
+
+impl<T: NetplayTransport> RollbackSession<T> {
+    pub fn new(mut consoles: [Lynx; PLAYERS], local_player: usize, transport: T, max_rollback: usize) -> Result<Self, &'static str> {
+        if local_player >= PLAYERS {
+            return Err("Invalid local player.");
+        }
+        if max_rollback == 0 {
+            return Err("Rollback window can't be empty.");
+        }
+        let cable = ComlynxCable::default();
+        for lynx in consoles.iter_mut() {
+            lynx.set_comlynx_cable(&cable);
+        }
+        Ok(Self {
+            consoles,
+            cable,
+            local_player,
+            transport,
+            frame: 0,
+            first_frame: 0,
+            known_inputs: [LynxInput::default(); PLAYERS],
+            confirmed_inputs: [vec![], vec![]],
+            used_inputs: vec![],
+            snapshots: VecDeque::new(),
+            max_rollback,
+            rollbacks: 0,
+        })
+    }
+
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    pub fn local_player(&self) -> usize {
+        self.local_player
+    }
+
+    pub fn console(&self, player: usize) -> &Lynx {
+        &self.consoles[player]
+    }
+
+    pub fn local_console(&self) -> &Lynx {
+        &self.consoles[self.local_player]
+    }
+
+    pub fn rollbacks(&self) -> u64 {
+        self.rollbacks
+    }
+
+    pub fn transport(&self) -> &T {
+        &self.transport
+    }
+
+    pub fn advance_frame(&mut self, local_input: LynxInput) -> Result<(), &'static str> {
+        self.synchronize()?;
+
+        let packet = InputPacket { frame: self.frame, input: local_input };
+        self.set_confirmed_input(self.local_player, packet);
+        self.transport.send(packet);
+
+        self.simulate_frame()
+    }
+
+    /* Processes the inputs received so far, rolling back if any prediction was wrong. */
+    pub fn synchronize(&mut self) -> Result<(), &'static str> {
+        let remote_player = 1 - self.local_player;
+        let mut mismatch: Option<u64> = None;
+        let mut too_late = false;
+
+        while let Some(packet) = self.transport.receive() {
+            if packet.frame < self.first_frame {
+                too_late = true;
+                continue;
+            }
+            self.set_confirmed_input(remote_player, packet);
+            if let Some(used) = self.used_inputs.get((packet.frame - self.first_frame) as usize) {
+                if used[remote_player] != packet.input {
+                    mismatch = Some(mismatch.map_or(packet.frame, |f| f.min(packet.frame)));
+                }
+            }
+        }
+
+        match mismatch {
+            _ if too_late => Err("Rollback window exceeded."),
+            None => Ok(()),
+            Some(frame) => self.rollback(frame),
+        }
+    }
+
+    fn set_confirmed_input(&mut self, player: usize, packet: InputPacket) {
+        let inputs = &mut self.confirmed_inputs[player];
+        let frame = (packet.frame - self.first_frame) as usize;
+        if inputs.len() <= frame {
+            inputs.resize(frame + 1, None);
+        }
+        inputs[frame] = Some(packet.input);
+    }
+
+    fn input(&self, player: usize, frame: u64) -> LynxInput {
+        let inputs = &self.confirmed_inputs[player];
+        let last = ((frame - self.first_frame) as usize).min(inputs.len().saturating_sub(1));
+        inputs.iter().take(last + 1).rev().find_map(|i| *i).unwrap_or(self.known_inputs[player])
+    }
+
+    fn rollback(&mut self, frame: u64) -> Result<(), &'static str> {
+        trace!("Rollback from frame {} to {}", self.frame, frame);
+        while self.snapshots.back().is_some_and(|s| s.frame > frame) {
+            self.snapshots.pop_back();
+        }
+        let snapshot = match self.snapshots.pop_back() {
+            Some(s) if s.frame == frame => s,
+            _ => return Err("Rollback window exceeded."),
+        };
+        self.restore(&snapshot);
+
+        let target = self.frame;
+        self.frame = frame;
+        self.used_inputs.truncate((frame - self.first_frame) as usize);
+        while self.frame < target {
+            self.simulate_frame()?;
+        }
+        self.rollbacks += 1;
+        Ok(())
+    }
+
+    fn simulate_frame(&mut self) -> Result<(), &'static str> {
+        self.save();
+
+        let inputs = [self.input(0, self.frame), self.input(1, self.frame)];
+        for (lynx, input) in self.consoles.iter_mut().zip(inputs) {
+            lynx.set_input(input, InputOrientation::Screen, InputLatch::Immediate);
+        }
+        self.used_inputs.push(inputs);
+
+        let frame_count = self.consoles[0].mikey().video().frame_count();
+        while self.consoles[0].mikey().video().frame_count() == frame_count {
+            for lynx in self.consoles.iter_mut() {
+                lynx.tick();
+            }
+        }
+        self.frame += 1;
+        Ok(())
+    }
+
+    fn save(&mut self) {
+        if self.snapshots.len() >= self.max_rollback {
+            self.snapshots.pop_front();
+        }
+        self.snapshots.push_back(FrameSnapshot {
+            frame: self.frame,
+            consoles: [self.consoles[0].snapshot(), self.consoles[1].snapshot()],
+            line: self.cable.status(),
+        });
+        self.trim_inputs();
+    }
+
+    /* Drops the inputs of the frames before the oldest snapshot. */
+    fn trim_inputs(&mut self) {
+        let Some(oldest) = self.snapshots.front().map(|s| s.frame) else {
+            return;
+        };
+        if oldest <= self.first_frame {
+            return;
+        }
+        let count = (oldest - self.first_frame) as usize;
+        for player in 0..PLAYERS {
+            self.known_inputs[player] = self.input(player, oldest - 1);
+            let inputs = &mut self.confirmed_inputs[player];
+            inputs.drain(..count.min(inputs.len()));
+        }
+        self.used_inputs.drain(..count.min(self.used_inputs.len()));
+        self.first_frame = oldest;
+    }
+
+    /* The consoles keep their cable, the level of the wire goes back to the snapshot one. */
+    fn restore(&mut self, snapshot: &FrameSnapshot) {
+        for (lynx, s) in self.consoles.iter_mut().zip(snapshot.consoles.iter()) {
+            lynx.restore(s);
+        }
+        self.cable.set(snapshot.line);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_console() -> Lynx {
+        // BS93 executable copying JOYSTICK to $0300 in a loop
+        const JOY_COPY: [u8; 19] = [
+            0x80, 0x08, 0x02, 0x00, 0x00, 0x13, b'B', b'S', b'9', b'3',
+            0xAD, 0xB0, 0xFC, 0x8D, 0x00, 0x03, 0x4C, 0x00, 0x02,
+        ];
+        let mut lynx = Lynx::new();
+        lynx.load_cart_from_slice(&JOY_COPY).unwrap();
+        lynx
+    }
+
+    fn state<S: Serialize>(s: &S) -> Vec<u8> {
+        let mut buf = vec![0; 0x20000];
+        let len = postcard::to_slice(s, &mut buf).unwrap().len();
+        buf.truncate(len);
+        buf
+    }
+
+    fn assert_same_consoles(a: &Lynx, b: &Lynx) {
+        assert_eq!(a.ticks(), b.ticks());
+        assert!(state(a.ram()) == state(b.ram()));
+        assert!(state(a.suzy()) == state(b.suzy()));
+        assert!(state(a.mikey()) == state(b.mikey()));
+    }
+
+    #[test]
+    fn loopback_transport() {
+        let (mut a, mut b) = LoopbackTransport::pair();
+        let packet = InputPacket { frame: 3, input: LynxInput { up: true, ..Default::default() } };
+        a.send(packet);
+        assert_eq!(a.receive(), None);
+        assert_eq!(b.receive(), Some(packet));
+        assert_eq!(b.receive(), None);
+    }
+
+    #[test]
+    fn late_inputs_are_resimulated() {
+        const FRAMES: u64 = 6;
+        let p0 = |f: u64| LynxInput { a: f >= 2, ..Default::default() };
+        let p1 = |f: u64| LynxInput { up: f >= 1, b: f >= 4, ..Default::default() };
+
+        // reference: a single session getting the remote inputs in time
+        let (t_ref, mut t_remote) = LoopbackTransport::pair();
+        let mut reference = RollbackSession::new([test_console(), test_console()], 0, t_ref, 8).unwrap();
+        for f in 0..FRAMES {
+            t_remote.send(InputPacket { frame: f, input: p1(f) });
+            reference.advance_frame(p0(f)).unwrap();
+        }
+        assert_eq!(reference.rollbacks(), 0);
+        assert_ne!(reference.console(0).ram().get(0x0300), 0);
+
+        // player 0 runs ahead predicting player 1, then catches up on its inputs
+        let (t0, t1) = LoopbackTransport::pair();
+        let mut peer0 = RollbackSession::new([test_console(), test_console()], 0, t0, 8).unwrap();
+        let mut peer1 = RollbackSession::new([test_console(), test_console()], 1, t1, 8).unwrap();
+        for f in 0..FRAMES {
+            peer0.advance_frame(p0(f)).unwrap();
+        }
+        for f in 0..FRAMES {
+            peer1.advance_frame(p1(f)).unwrap();
+        }
+        peer0.synchronize().unwrap();
+
+        assert_eq!(peer0.rollbacks(), 1);
+        assert_eq!(peer1.rollbacks(), 0);
+        for player in 0..PLAYERS {
+            assert_same_consoles(peer0.console(player), reference.console(player));
+            assert_same_consoles(peer1.console(player), reference.console(player));
+        }
+    }
+
+    #[test]
+    fn inputs_are_trimmed() {
+        let (t0, mut t1) = LoopbackTransport::pair();
+        let mut peer0 = RollbackSession::new([test_console(), test_console()], 0, t0, 2).unwrap();
+        let up = LynxInput { up: true, ..Default::default() };
+        t1.send(InputPacket { frame: 0, input: up });
+        for _ in 0..10 {
+            peer0.advance_frame(LynxInput::default()).unwrap();
+        }
+        assert_eq!(peer0.first_frame, 8);
+        assert!(peer0.confirmed_inputs.iter().all(|i| i.len() <= 2));
+        assert_eq!(peer0.used_inputs.len(), 2);
+        // the remote input of frame 0 is still the prediction
+        assert_eq!(peer0.input(1, peer0.frame()), up);
+        assert_eq!(peer0.used_inputs[1], [LynxInput::default(), up]);
+    }
+
+    #[test]
+    fn rollback_window_exceeded() {
+        let (t0, mut t1) = LoopbackTransport::pair();
+        let mut peer0 = RollbackSession::new([test_console(), test_console()], 0, t0, 2).unwrap();
+        for _ in 0..4 {
+            peer0.advance_frame(LynxInput::default()).unwrap();
+        }
+        t1.send(InputPacket { frame: 0, input: LynxInput { up: true, ..Default::default() } });
+        assert_eq!(peer0.synchronize(), Err("Rollback window exceeded."));
+    }
+}
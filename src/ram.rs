@@ -5,7 +5,31 @@ use super::bus::*;
 
 pub const RAM_MAX: u16 = 0xffff;
 
-#[derive(Serialize, Deserialize)]
+/* Content of the RAM at power on. The DRAM comes up with whatever its cells hold, the boot ROM clears
+it but programs loaded without it, or carts reading memory they never wrote, see these values. */
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum RamInit {
+    Zero,
+    /* Every byte is 0xFF. */
+    #[default]
+    Ones,
+    /* Pseudo random bytes, always the same for a given seed. */
+    Random(u64),
+    /* Runs of 0x00 and 0xFF alternating every 128 bytes, for programs relying on memory being neither
+    blank nor uniform. */
+    Stripes,
+}
+
+/* SplitMix64, good enough to look like noise and reproducible without depending on a crate. */
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Ram {
     data: SharedMemory,
     addr_r: u16,
@@ -17,6 +41,10 @@ pub struct Ram {
 
 impl Ram {
     pub fn new() -> Ram {
+        Ram::with_init(RamInit::default())
+    }
+
+    pub fn with_init(init: RamInit) -> Ram {
         let mut r = Ram {
             data: SharedMemory::new((RAM_MAX as usize) + 1, 0xFF),
             ticks_to_done: -1,
@@ -25,10 +53,43 @@ impl Ram {
             write: false,
             ticks: 0,
         };
-        r.data[MMC_ADDR as usize] = 0;
+        r.power_on(init);
         r
     }
 
+    /* Fills the RAM in place, a frontend sharing it keeps a valid pointer. */
+    pub fn power_on(&mut self, init: RamInit) {
+        match init {
+            RamInit::Zero => self.data.fill(0),
+            RamInit::Ones => self.data.fill(0xFF),
+            RamInit::Random(seed) => {
+                let mut state = seed;
+                for addr in (0..=RAM_MAX as usize).step_by(8) {
+                    let v = splitmix64(&mut state).to_le_bytes();
+                    for (i, b) in v.iter().enumerate() {
+                        self.data[addr + i] = *b;
+                    }
+                }
+            }
+            RamInit::Stripes => {
+                for addr in 0..=RAM_MAX as usize {
+                    self.data[addr] = if addr & 0x80 == 0 { 0x00 } else { 0xFF };
+                }
+            }
+        }
+        self.data[MMC_ADDR as usize] = 0;
+        self.reset();
+    }
+
+    /* Drops the access in progress, the content is kept. */
+    pub fn reset(&mut self) {
+        self.ticks_to_done = -1;
+        self.addr_r = 0;
+        self.data_r = 0;
+        self.write = false;
+        self.ticks = 0;
+    }
+
     #[inline]
     pub fn get(&self, addr: u16) -> u8 {
         self.data[addr as usize]
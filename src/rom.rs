@@ -1,5 +1,5 @@
 use alloc::vec::Vec;
-use log::trace;
+use log::{trace, warn};
 use serde::{Deserialize, Serialize};
 use crate::{bus::{Bus, BusStatus}, consts::*};
 
@@ -10,7 +10,125 @@ const ROM_PAGE_READ_TICKS: i8 = 4;
 // Courtesy of https://bjoern.spruck.net/lynx/
 const FREE_BOOTROM: [u8; 512] = [0xA2, 0x0E, 0xBC, 0x10, 0xFE, 0xBD, 0x1F, 0xFE, 0x99, 0x00, 0xFD, 0xCA, 0x10, 0xF4, 0x80, 0x1E,0x00, 0x01, 0x08, 0x09, 0x20, 0x28, 0x30, 0x38, 0x44, 0x50, 0x8A, 0x8B, 0x8C, 0x92, 0x93, 0x9E,0x18, 0x68, 0x1F, 0x00, 0x00, 0x00, 0x00, 0x00, 0xFF, 0x1A, 0x0B, 0x04, 0x0D, 0x29, 0xA9, 0x0B,0x85, 0x05, 0xA9, 0x1A, 0x85, 0x06, 0xA9, 0x0D, 0x85, 0x07, 0xA9, 0xF8, 0x85, 0x1A, 0xA9, 0x9A,0x85, 0x18, 0xA9, 0x01, 0x85, 0x19, 0xA9, 0x01, 0x85, 0x1B, 0x20, 0xE1, 0xFE, 0x20, 0xBE, 0xFE,0x85, 0x1A, 0xA9, 0x01, 0x85, 0x0B, 0x20, 0xAB, 0xFF, 0xA2, 0x07, 0x20, 0xA0, 0xFF, 0x95, 0x1C,0xCA, 0x10, 0xF8, 0xA2, 0x07, 0xB5, 0x1C, 0xDD, 0xB6, 0xFE, 0xD0, 0x09, 0xCA, 0x10, 0xF6, 0xA9,0x00, 0xA2, 0x04, 0x80, 0x22, 0xA2, 0x07, 0xB5, 0x1C, 0xDD, 0xAE, 0xFE, 0xD0, 0x07, 0xCA, 0x10,0xF6, 0x64, 0x1B, 0x80, 0x16, 0xA2, 0x07, 0xB5, 0x1C, 0xDD, 0xA6, 0xFE, 0xD0, 0x0D, 0xCA, 0x10,0xF6, 0xA9, 0x80, 0xA2, 0x03, 0x64, 0x1B, 0x85, 0x18, 0x86, 0x19, 0xA9, 0x01, 0x20, 0x64, 0xFF,0x20, 0x8D, 0xFF, 0x6C, 0x12, 0x00, 0x02, 0x00, 0x02, 0x00, 0x00, 0x02, 0x00, 0x00, 0x01, 0xF8,0x02, 0x00, 0x00, 0x00, 0x08, 0x01, 0x00, 0x70, 0x02, 0x16, 0x00, 0x02, 0x00, 0x00, 0xA2, 0x00,0xBD, 0xCC, 0xEF, 0xDD, 0xCC, 0xF1, 0xD0, 0x06, 0xCA, 0xD0, 0xF5, 0xA9, 0xFE, 0x60, 0xA2, 0x00,0xBD, 0xCC, 0xEF, 0xDD, 0xCC, 0xF3, 0xD0, 0x06, 0xCA, 0xD0, 0xF5, 0xA9, 0xFC, 0x60, 0xA9, 0xF8,0x60, 0xA9, 0xCC, 0x85, 0x16, 0xA9, 0xEF, 0x85, 0x17, 0x64, 0x0B, 0x20, 0xAB, 0xFF, 0xA2, 0xFF,0xA0, 0xF7, 0x20, 0x8D, 0xFF, 0xAD, 0xCC, 0xEF, 0xC9, 0xFF, 0xD0, 0x0E, 0xA9, 0xD7, 0x85, 0x18,0xA9, 0x00, 0x85, 0x19, 0x20, 0x1D, 0xFF, 0x4C, 0x00, 0xF0, 0xC9, 0xFD, 0xD0, 0x01, 0x60, 0xC9,0xFB, 0xD0, 0x09, 0xA9, 0x00, 0x85, 0x18, 0xA9, 0x02, 0x85, 0x19, 0x60, 0x60, 0x64, 0x0B, 0x20,0xAB, 0xFF, 0xAA, 0x4A, 0x4A, 0x4A, 0x4A, 0x4A, 0x48, 0x8A, 0x0A, 0x0A, 0x0A, 0x18, 0x65, 0x18,0x49, 0xFF, 0xAA, 0x68, 0x65, 0x19, 0x49, 0xFF, 0xA8, 0x20, 0x82, 0xFF, 0xA2, 0x00, 0xA0, 0x08,0x20, 0xA0, 0xFF, 0x95, 0x0E, 0xE8, 0x88, 0xD0, 0xF7, 0xA5, 0x1B, 0xF0, 0x16, 0xA9, 0xFF, 0xAA,0x45, 0x14, 0x85, 0x14, 0x8A, 0x45, 0x15, 0x85, 0x15, 0x8A, 0x45, 0x0F, 0x85, 0x0F, 0x8A, 0x45,0x10, 0x85, 0x10, 0x60, 0x20, 0x1D, 0xFF, 0xA5, 0x12, 0x85, 0x16, 0xA5, 0x13, 0x85, 0x17, 0xA5,0x0E, 0x85, 0x0B, 0x20, 0xAB, 0xFF, 0xA6, 0x0F, 0xA4, 0x10, 0x20, 0x82, 0xFF, 0xA6, 0x14, 0xA4,0x15, 0x60, 0xE8, 0xD0, 0x03, 0xC8, 0xF0, 0x57, 0x20, 0xA0, 0xFF, 0x80, 0xF5, 0xE8, 0xD0, 0x03,0xC8, 0xF0, 0x4C, 0x20, 0xA0, 0xFF, 0x92, 0x16, 0xE6, 0x16, 0xD0, 0xF1, 0xE6, 0x17, 0x80, 0xED,0xAD, 0xB2, 0xFC, 0xE6, 0x0C, 0xD0, 0x38, 0xE6, 0x0D, 0xD0, 0x34, 0x48, 0xDA, 0x5A, 0xA5, 0x05,0x29, 0xFC, 0xA8, 0x09, 0x02, 0xAA, 0xA5, 0x0B, 0xE6, 0x0B, 0x38, 0x80, 0x0B, 0x90, 0x04, 0x8E,0x8B, 0xFD, 0x18, 0xE8, 0x8E, 0x87, 0xFD, 0xCA, 0x8E, 0x87, 0xFD, 0x2A, 0x8C, 0x8B, 0xFD, 0xD0,0xEC, 0xA5, 0x05, 0x8D, 0x8B, 0xFD, 0x64, 0x0C, 0xA5, 0x1A, 0x85, 0x0D, 0x7A, 0xFA, 0x68, 0x60,0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x80, 0x00, 0x30, 0x00, 0xFE, 0x00, 0xFE];
 
-#[derive(Serialize, Deserialize)]
+/* Stand-in ROM for the HLE boot, only providing the routine at $FE00 selecting the cart block given in A,
+used by the cart loaders. NMI and IRQ point to a RTI, reset to an endless loop. */
+const HLE_BLOCK_SELECT: [u8; 40] = [
+    0xDA,               // PHX
+    0x5A,               // PHY
+    0xA0, 0x08,         // LDY #8
+    0x0A,               // ASL A
+    0x48,               // PHA
+    0xA9, 0x0B,         // LDA #$0B  CAD high
+    0xB0, 0x02,         // BCS +2
+    0xA9, 0x09,         // LDA #$09  CAD low
+    0x8D, 0x8B, 0xFD,   // STA IODAT
+    0xA9, 0x0B,         // LDA #$0B
+    0x8D, 0x87, 0xFD,   // STA SYSCTL1  CAS high
+    0xA9, 0x0A,         // LDA #$0A
+    0x8D, 0x87, 0xFD,   // STA SYSCTL1  CAS low, the bit is shifted in
+    0x68,               // PLA
+    0x88,               // DEY
+    0xD0, 0xE7,         // BNE -25
+    0xA9, 0x0B,         // LDA #$0B
+    0x8D, 0x8B, 0xFD,   // STA IODAT
+    0x7A,               // PLY
+    0xFA,               // PLX
+    0x60,               // RTS
+    0x40,               // RTI
+    0x80, 0xFE,         // BRA *
+];
+const HLE_RTI: u16 = 0xFE25;
+const HLE_HALT: u16 = 0xFE26;
+const HLE_BOOTROM: [u8; ROM_SIZE] = hle_bootrom();
+
+const fn hle_bootrom() -> [u8; ROM_SIZE] {
+    let mut data = [0; ROM_SIZE];
+    let mut i = 0;
+    while i < HLE_BLOCK_SELECT.len() {
+        data[i] = HLE_BLOCK_SELECT[i];
+        i += 1;
+    }
+    let (rti, halt) = (HLE_RTI.to_le_bytes(), HLE_HALT.to_le_bytes());
+    data[0x1FA] = rti[0];
+    data[0x1FB] = rti[1];
+    data[0x1FC] = halt[0];
+    data[0x1FD] = halt[1];
+    data[0x1FE] = rti[0];
+    data[0x1FF] = rti[1];
+    data
+}
+
+/* CRC32 of the known boot ROM dumps, named after the dump rather than a console model: nothing documents
+which model each dump comes from. 0x0D973C9D is `[BIOS] Atari Lynx (World)` in the No-Intro dat, the file
+usually distributed as `lynxboot.img`. */
+const KNOWN_ROMS: [(u32, RomKind); 2] = [
+    (0x0D973C9D, RomKind::LynxBoot),
+    (0xDB8B0ABF, RomKind::Free),
+];
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RomKind {
+    LynxBoot,
+    Free,
+    Hle,
+    Unknown,
+}
+
+impl RomKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            RomKind::LynxBoot => "[BIOS] Atari Lynx (World)",
+            RomKind::Free => "Free boot ROM",
+            RomKind::Hle => "HLE boot stub",
+            RomKind::Unknown => "Unknown boot ROM",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RomInfo {
+    kind: RomKind,
+    crc32: u32,
+    reset_vector: u16,
+}
+
+impl RomInfo {
+    pub fn kind(&self) -> RomKind {
+        self.kind
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.kind.name()
+    }
+
+    pub fn crc32(&self) -> u32 {
+        self.crc32
+    }
+
+    pub fn reset_vector(&self) -> u16 {
+        self.reset_vector
+    }
+
+    pub fn is_known(&self) -> bool {
+        self.kind != RomKind::Unknown
+    }
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xFFFFFFFFu32;
+    for b in data {
+        crc ^= *b as u32;
+        for _ in 0..8 {
+            crc = match crc & 1 {
+                0 => crc >> 1,
+                _ => (crc >> 1) ^ 0xEDB88320,
+            };
+        }
+    }
+    !crc
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Rom {
     data: Vec<u8>,
     addr_r: u16,
@@ -35,10 +153,53 @@ impl Rom {
         if data.len() != ROM_SIZE {
             return Err("ROM file non valid.");
         }
+        if data.iter().all(|b| *b == data[0]) {
+            return Err("ROM file is blank, bad dump.");
+        }
         r.data = data.to_vec();
+        r.check_reset_vector()?;
+        let info = r.info();
+        match info.is_known() {
+            true => trace!("{} (CRC32 {:08X})", info.name(), info.crc32()),
+            false => warn!("Unknown boot ROM (CRC32 {:08X}), it may be a bad dump.", info.crc32()),
+        }
         Ok(r)
     }
 
+    pub fn info(&self) -> RomInfo {
+        let crc32 = crc32(&self.data);
+        let kind = match KNOWN_ROMS.iter().find(|(crc, _)| *crc == crc32) {
+            Some((_, kind)) => *kind,
+            None if self.is_hle() => RomKind::Hle,
+            None => RomKind::Unknown,
+        };
+        RomInfo { kind, crc32, reset_vector: self.reset_vector() }
+    }
+
+    fn reset_vector(&self) -> u16 {
+        u16::from_le_bytes([self.data[0x1FC], self.data[0x1FD]])
+    }
+
+    /* At power on the ROM is mapped and the RAM is garbage, a reset vector out of the ROM code can only hang the CPU. */
+    pub fn check_reset_vector(&self) -> Result<(), &'static str> {
+        match self.reset_vector() {
+            ROM_ADDR..=MMC_ADDR_B => Ok(()),
+            _ => Err("ROM reset vector doesn't point to the ROM code."),
+        }
+    }
+
+    pub fn hle() -> Rom {
+        Rom { data: HLE_BOOTROM.to_vec(), ..Default::default() }
+    }
+
+    pub fn is_free_bootrom(&self) -> bool {
+        self.data == FREE_BOOTROM
+    }
+
+    pub fn is_hle(&self) -> bool {
+        self.data == HLE_BOOTROM
+    }
+
     pub fn as_slice(&self) -> &[u8] {
         self.data.as_slice()
     }
@@ -85,4 +246,45 @@ impl Rom {
     pub fn ready(&self) -> bool {
         self.ticks_to_done == -1
     }
+
+    /* Drops the access in progress, the content is kept. */
+    pub fn reset(&mut self) {
+        self.addr_r = 0;
+        self.ticks_to_done = -1;
+        self.ticks = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identification() {
+        let info = Rom::default().info();
+        assert_eq!(info.kind(), RomKind::Free);
+        assert_eq!(info.reset_vector(), 0xFE00);
+        assert_eq!(Rom::hle().info().kind(), RomKind::Hle);
+
+        let mut patched = FREE_BOOTROM;
+        patched[0x100] ^= 0xFF;
+        let info = Rom::from_slice(&patched).unwrap().info();
+        assert_eq!(info.kind(), RomKind::Unknown);
+        assert_ne!(info.crc32(), Rom::default().info().crc32());
+    }
+
+    #[test]
+    fn bad_dumps() {
+        assert!(Rom::from_slice(&FREE_BOOTROM[..256]).is_err());
+        assert!(Rom::from_slice(&[0xFF; ROM_SIZE]).is_err());
+
+        let mut bad_vector = FREE_BOOTROM;
+        bad_vector[0x1FD] = 0x02;
+        assert_eq!(Rom::from_slice(&bad_vector).err(), Some("ROM reset vector doesn't point to the ROM code."));
+    }
+
+    #[test]
+    fn crc() {
+        assert_eq!(crc32(b"123456789"), 0xCBF43926);
+    }
 }
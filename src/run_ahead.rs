@@ -0,0 +1,148 @@
+use alloc::vec::Vec;
+use crate::consts::CRYSTAL_FREQ;
+use crate::lynx::Lynx;
+
+/* A frame never lasts more than this, even with the display timer stopped. */
+const MAX_FRAME_TICKS: u64 = CRYSTAL_FREQ as u64;
+
+/* Hides the input lag of the LCD era: every host frame, the console runs one frame for real, then a
+detached copy runs `frames` more frames with the same input and its picture is the one presented.
+Audio is only sampled on the real timeline, the copy is thrown away. */
+pub struct RunAhead {
+    frames: usize,
+    sample_rate: u32,
+    sample_acc: u32,
+    samples: Vec<(i16, i16)>,
+    screen: Vec<u8>,
+}
+
+impl RunAhead {
+    /* `sample_rate` is the rate audio samples are collected at, 0 doesn't collect any. */
+    pub fn new(frames: usize, sample_rate: u32) -> Self {
+        Self {
+            frames,
+            sample_rate: sample_rate.min(CRYSTAL_FREQ),
+            sample_acc: 0,
+            samples: Vec::new(),
+            screen: Vec::new(),
+        }
+    }
+
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    pub fn set_frames(&mut self, frames: usize) {
+        self.frames = frames;
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /* Runs a host frame with the input already set on `lynx`. `lynx` only moves forward by one frame. */
+    pub fn run_frame(&mut self, lynx: &mut Lynx) {
+        self.samples.clear();
+        run_frame(lynx, |l| {
+            if self.sample_rate == 0 {
+                return;
+            }
+            self.sample_acc += self.sample_rate;
+            if self.sample_acc >= CRYSTAL_FREQ {
+                self.sample_acc -= CRYSTAL_FREQ;
+                self.samples.push(l.audio_sample());
+            }
+        });
+
+        if self.frames == 0 {
+            self.screen.clone_from(lynx.screen_rgb());
+            return;
+        }
+        let mut ahead = lynx.clone();
+        for _ in 0..self.frames {
+            run_frame(&mut ahead, |_| ());
+        }
+        self.screen.clone_from(ahead.screen_rgb());
+    }
+
+    /* Picture of the last frame, `frames` frames ahead of the console. */
+    pub fn screen_rgb(&self) -> &Vec<u8> {
+        &self.screen
+    }
+
+    /* Audio of the last frame, from the real timeline. */
+    pub fn audio_samples(&self) -> &[(i16, i16)] {
+        &self.samples
+    }
+}
+
+fn run_frame<F: FnMut(&Lynx)>(lynx: &mut Lynx, mut on_tick: F) {
+    let frame_count = lynx.mikey().video().frame_count();
+    let start = lynx.ticks();
+    while lynx.mikey().video().frame_count() == frame_count && lynx.ticks() - start < MAX_FRAME_TICKS {
+        lynx.tick();
+        on_tick(lynx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::{InputLatch, InputOrientation, LynxInput};
+
+    // BS93 executable copying JOYSTICK to $0300 in a loop
+    const JOY_COPY: [u8; 19] = [
+        0x80, 0x08, 0x02, 0x00, 0x00, 0x13, b'B', b'S', b'9', b'3',
+        0xAD, 0xB0, 0xFC, 0x8D, 0x00, 0x03, 0x4C, 0x00, 0x02,
+    ];
+
+    fn console() -> Lynx {
+        let mut lynx = Lynx::new();
+        lynx.load_cart_from_slice(&JOY_COPY).unwrap();
+        lynx
+    }
+
+    #[test]
+    fn real_timeline_is_untouched() {
+        let mut reference = console();
+        let mut lynx = console();
+        let mut run_ahead = RunAhead::new(2, 22050);
+        for f in 0..8 {
+            let input = LynxInput { a: f >= 5, ..Default::default() };
+            lynx.set_input(input, InputOrientation::Screen, InputLatch::Immediate);
+            reference.set_input(input, InputOrientation::Screen, InputLatch::Immediate);
+            run_ahead.run_frame(&mut lynx);
+            run_frame(&mut reference, |_| ());
+            assert_eq!(lynx.ticks(), reference.ticks());
+            assert_eq!(lynx.mikey().video().frame_count(), reference.mikey().video().frame_count());
+        }
+        assert_eq!(lynx.ram().get(0x0300), reference.ram().get(0x0300));
+        assert_ne!(lynx.ram().get(0x0300), 0);
+    }
+
+    #[test]
+    fn screen_is_ahead() {
+        let mut lynx = console();
+        let mut run_ahead = RunAhead::new(2, 0);
+        run_ahead.run_frame(&mut lynx);
+        assert!(run_ahead.audio_samples().is_empty());
+
+        let mut ahead = console();
+        for _ in 0..3 {
+            run_frame(&mut ahead, |_| ());
+        }
+        assert!(run_ahead.screen_rgb() == ahead.screen_rgb());
+        assert_eq!(lynx.mikey().video().frame_count() + 2, ahead.mikey().video().frame_count());
+    }
+
+    #[test]
+    fn audio_rate() {
+        let mut lynx = console();
+        let mut run_ahead = RunAhead::new(1, 48000);
+        run_ahead.run_frame(&mut lynx);
+        let start = lynx.ticks();
+        run_ahead.run_frame(&mut lynx);
+        let expected = (lynx.ticks() - start) * 48000 / CRYSTAL_FREQ as u64;
+        assert!(run_ahead.audio_samples().len().abs_diff(expected as usize) <= 1);
+    }
+}
@@ -41,6 +41,15 @@ impl SharedMemory {
     }
 }
 
+impl Clone for SharedMemory {
+    fn clone(&self) -> Self {
+        let ptr = self.data.get();
+        Self {
+            data: UnsafeCell::new(unsafe { (*ptr).clone() })
+        }
+    }
+}
+
 impl Default for SharedMemory {
     fn default() -> Self {
         SharedMemory::new(0, 0xFF)
@@ -51,7 +60,7 @@ impl Index<usize> for SharedMemory {
     type Output = u8;
     fn index(&self, i: usize) -> &u8 {
         let ptr = self.data.get();
-        unsafe { &(*ptr)[i] }
+        unsafe { &(&(*ptr))[i] }
     }
 }
 
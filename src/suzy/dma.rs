@@ -0,0 +1,111 @@
+use ram::Ram;
+use serde::{Deserialize, Serialize};
+use super::*;
+
+/* Accounts the RAM cycles of the sprite engine.
+Reads in the page of the previous access are page mode cycles, the others and all the writes are normal cycles.
+Losing the bus to the video DMA closes the page. The ticks are collected by the renderer at the end of
+each of its steps and become the delay before the next one. */
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SuzyDma {
+    page: Option<u8>,
+    ticks: u16,
+    total_ticks: u64,
+    first_page_read: Option<bool>,
+}
+
+impl SuzyDma {
+    pub fn new() -> Self {
+        Self {
+            page: None,
+            ticks: 0,
+            total_ticks: 0,
+            first_page_read: None,
+        }
+    }
+
+    pub fn peek(&mut self, ram: &Ram, addr: u16) -> u8 {
+        let page = (addr >> 8) as u8;
+        let page_read = self.page == Some(page);
+        if page_read {
+            self.add_ticks(SUZY_RAM_PAGE_READ_TICKS);
+        } else {
+            self.add_ticks(SUZY_RAM_NORMAL_READ_TICKS);
+        }
+        self.first_page_read.get_or_insert(page_read);
+        self.page = Some(page);
+        ram.get(addr)
+    }
+
+    pub fn poke(&mut self, ram: &mut Ram, addr: u16, data: u8) {
+        self.add_ticks(SUZY_RAM_WRITE_TICKS);
+        self.first_page_read.get_or_insert(false);
+        self.page = Some((addr >> 8) as u8);
+        ram.set(addr, data);
+    }
+
+    /* Work of the sprite engine that doesn't use the bus. */
+    pub fn add_ticks(&mut self, ticks: u16) {
+        self.ticks += ticks;
+        self.total_ticks += ticks as u64;
+    }
+
+    pub fn close_page(&mut self) {
+        self.page = None;
+    }
+
+    /* Ticks spent since the last call. */
+    pub fn take_ticks(&mut self) -> u16 {
+        self.first_page_read = None;
+        core::mem::take(&mut self.ticks)
+    }
+
+    /* Whether the first RAM access since `take_ticks()` was a page mode read, `None` without any access. */
+    pub fn first_page_read(&self) -> Option<bool> {
+        self.first_page_read
+    }
+
+    /* A page mode read already accounted was a normal one, its page had been closed. */
+    pub fn miss_page(&mut self) -> u16 {
+        let ticks = SUZY_RAM_NORMAL_READ_TICKS - SUZY_RAM_PAGE_READ_TICKS;
+        self.total_ticks += ticks as u64;
+        ticks
+    }
+
+    /* Ticks spent by the sprite engine since power on, bus waits excluded. */
+    pub fn total_ticks(&self) -> u64 {
+        self.total_ticks
+    }
+}
+
+impl Default for SuzyDma {
+    fn default() -> Self {
+        SuzyDma::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /* "A page mode op-code read takes 4 ticks, a normal read or write to RAM takes 5 ticks." */
+    #[test]
+    fn page_mode() {
+        let mut ram = Ram::new();
+        let mut dma = SuzyDma::new();
+        dma.peek(&ram, 0x2000);
+        assert_eq!(dma.take_ticks(), 5);
+        dma.peek(&ram, 0x20FF);
+        assert_eq!(dma.take_ticks(), 4);
+        dma.peek(&ram, 0x2100);
+        assert_eq!(dma.take_ticks(), 5);
+        dma.poke(&mut ram, 0x2101, 0x12);
+        assert_eq!(dma.take_ticks(), 5);
+        assert_eq!(dma.peek(&ram, 0x2101), 0x12);
+        assert_eq!(dma.take_ticks(), 4);
+        dma.close_page();
+        dma.peek(&ram, 0x2102);
+        assert_eq!(dma.take_ticks(), 5);
+        assert_eq!(dma.total_ticks(), 3 * 5 + 2 * 4 + 5);
+    }
+}
@@ -1,8 +1,12 @@
 use log::trace;
 
-use crate::{consts::*, suzy::{SprSysR, SprSysW}};
+use crate::{consts::*, suzy::{SprSysR, SprSysW, SuzyTask}};
 use super::SuzyRegisters;
 
+/* The multiplier adds CD for every bit of AB, the divider subtracts NP for every bit of EFGH. */
+const MULT_STEPS: u32 = 16;
+const DIV_STEPS: u32 = 32;
+
 pub fn convert_sign(mut v: u16) -> (u16, i8)  {
     /* "
     In signed multiply, the hardware thinks that 8000 is a positive number. [...]
@@ -27,23 +31,24 @@ pub fn divide(regs: &mut SuzyRegisters) {
     regs.sprsys_r_disable_flag(SprSysR::math_warning);
     regs.sprsys_r_disable_flag(SprSysR::math_carry);
 
-    if 0 == np { // "The number in the dividend as a result of a divide by zero is 'FFFFFFFF (BigNum)."
-        trace!("MATH: divide by zero efgh:0x{:08x} / np:0x{:04x} -> abcd:0xffffffff, jklm: 0x0", efgh, np);
-        regs.set_abcd(0xffffffff);
-        regs.set_jklm(0);
-        regs.sprsys_r_enable_flag(SprSysR::math_warning);
-        regs.sprsys_r_enable_flag(SprSysR::math_carry);
-    }
-    else {
-        let abcd = efgh / np;
-        let jklm = efgh % np;
-        trace!("MATH: divide efgh:0x{:08x} / np:0x{:04x} -> abcd:0x{:08x}, jklm: 0x{:08x}", efgh, np, abcd, jklm);
-        regs.set_abcd(abcd);
-        regs.set_jklm(jklm);
-    
-        trace!("D;0x{:08X};0x{:04X};0x{:08X};0x{:08X}\n", efgh, np, abcd, jklm);
+    match efgh.checked_div(np) {
+        None => { // "The number in the dividend as a result of a divide by zero is 'FFFFFFFF (BigNum)."
+            trace!("MATH: divide by zero efgh:0x{:08x} / np:0x{:04x} -> abcd:0xffffffff, jklm: 0x0", efgh, np);
+            regs.set_abcd(0xffffffff);
+            regs.set_jklm(0);
+            regs.sprsys_r_enable_flag(SprSysR::math_warning);
+            regs.sprsys_r_enable_flag(SprSysR::math_carry);
+        }
+        Some(abcd) => {
+            let jklm = efgh % np;
+            trace!("MATH: divide efgh:0x{:08x} / np:0x{:04x} -> abcd:0x{:08x}, jklm: 0x{:08x}", efgh, np, abcd, jklm);
+            regs.set_abcd(abcd);
+            regs.set_jklm(jklm);
+
+            trace!("D;0x{:08X};0x{:04X};0x{:08X};0x{:08X}\n", efgh, np, abcd, jklm);
+        }
     }
-    
+
     regs.sprsys_r_disable_flag(SprSysR::math_working);
 }
 
@@ -91,18 +96,17 @@ pub fn set_matha(regs: &mut SuzyRegisters) {
         let (v, s) = convert_sign(regs.ab());
         regs.set_ab(v);
         regs.set_sign_ab(s);
-        regs.set_task_ticks_delay(SUZY_MULT_SIGN_TICKS);
+        start(regs, SUZY_MULT_SIGN_TICKS);
     } 
     else if regs.sprsys_w_is_flag_set(SprSysW::accumulate) {
         regs.set_sign_ab(1);
-        regs.set_task_ticks_delay(SUZY_MULT_SIGN_TICKS);
+        start(regs, SUZY_MULT_SIGN_TICKS);
     } 
     else {
         regs.set_sign_ab(1);
-        regs.set_task_ticks_delay(SUZY_MULT_NON_SIGN_TICKS);
+        start(regs, SUZY_MULT_NON_SIGN_TICKS);
     }
     regs.backup_cd();
-    regs.sprsys_r_enable_flag(SprSysR::math_working);
     regs.reset_ir();
 }
 
@@ -123,9 +127,8 @@ pub fn set_mathc(regs: &mut SuzyRegisters) {
 pub fn set_mathe(regs: &mut SuzyRegisters) {
     trace!("[MATHE] = 0x{:02x}", regs.data_r() as u8);
     regs.set_data(MATHE, regs.data_r() as u8);
-    regs.sprsys_r_enable_flag(SprSysR::math_working);
     // "Divides take 176 + 14*N ticks where N is the number of most significant zeros in the divisor."
-    regs.set_task_ticks_delay(176_u16 + 14 * regs.np().leading_zeros() as u16);
+    start(regs, 176_u16 + 14 * regs.np().leading_zeros() as u16);
     regs.reset_ir();
 }
 
@@ -135,4 +138,51 @@ pub fn set_mathm(regs: &mut SuzyRegisters) { // "The write to 'M' will clear the
     regs.set_data(MATHL, 0);
     regs.sprsys_r_disable_flag(SprSysR::math_warning);
     regs.reset_ir();
-}
\ No newline at end of file
+}
+
+/* Starts an operation of `ticks`, the results are built while it runs. */
+fn start(regs: &mut SuzyRegisters, ticks: u16) {
+    regs.set_task_ticks_delay(ticks);
+    regs.set_math_ticks(ticks);
+    regs.sprsys_r_enable_flag(SprSysR::math_working);
+}
+
+/* Steps of the operation done so far, the last one completes with the task. */
+fn steps_done(regs: &SuzyRegisters, steps: u32) -> u32 {
+    let ticks = regs.math_ticks() as u32;
+    let elapsed = ticks - (regs.task_ticks_delay() as u32).min(ticks);
+    steps * elapsed / (ticks + 1)
+}
+
+fn partial_product(regs: &SuzyRegisters) -> u32 {
+    let mask = (1_u32 << steps_done(regs, MULT_STEPS)) - 1;
+    (regs.ab() as u32 & mask) * regs.tmp_cd() as u32
+}
+
+fn partial_quotient(regs: &SuzyRegisters) -> (u32, u32) {
+    let efgh = regs.efgh();
+    let np = regs.np() as u32;
+    let mut abcd: u32 = 0;
+    let mut jklm: u32 = 0;
+    for bit in (DIV_STEPS - steps_done(regs, DIV_STEPS)..DIV_STEPS).rev() {
+        jklm = (jklm << 1) | ((efgh >> bit) & 1);
+        if jklm >= np {
+            jklm -= np;
+            abcd |= 1 << bit;
+        }
+    }
+    (abcd, jklm)
+}
+
+/* The results are built in their registers, reading them before the end of the operation returns
+what has been computed so far. Other registers read as is. */
+pub fn peek(regs: &SuzyRegisters, addr: u16) -> u8 {
+    let (v, base) = match (regs.task(), addr) {
+        (SuzyTask::Multiply, MATHH..=MATHE) => (partial_product(regs), MATHH),
+        (SuzyTask::Divide, MATHD..=MATHA) => (partial_quotient(regs).0, MATHD),
+        (SuzyTask::Divide, MATHM..=MATHJ) => (partial_quotient(regs).1, MATHM),
+        _ => return regs.data(addr),
+    };
+    trace!("MATH: in progress read 0x{:04x}", addr);
+    (v >> ((addr - base) * 8)) as u8
+}
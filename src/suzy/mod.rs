@@ -2,7 +2,10 @@ pub mod renderer;
 pub mod math;
 pub mod sprite_data;
 pub mod registers;
+pub mod dma;
+pub mod unsafe_access;
 
+use alloc::boxed::Box;
 use bus::{Bus, BusStatus};
 use consts::*;
 use log::trace;
@@ -11,6 +14,7 @@ use ram::Ram;
 use renderer::*;
 use registers::*;
 use serde::{Deserialize, Serialize};
+use unsafe_access::{UnsafeAccess, UnsafeAccessHook};
 use super::*;
 
 #[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
@@ -45,16 +49,21 @@ pub struct Suzy {
     pending_bus_request_ticks: i8,
     renderer: Renderer,
     registers: SuzyRegisters,
+    #[serde(skip)]
+    unsafe_access_hook: Option<Box<dyn UnsafeAccessHook>>,
 }
 
 impl Suzy {
     pub fn new() -> Self {
         let mut s = Self {
             ticks: 0,
-            request_monitor: true,
+            /* Level of the bus request line last seen, it is low at power on. Starting high would
+            miss the first request, the video DMA would then wait for the end of the sprite run. */
+            request_monitor: false,
             pending_bus_request_ticks: -1,
             renderer: Renderer::new(),
-            registers: SuzyRegisters::new()
+            registers: SuzyRegisters::new(),
+            unsafe_access_hook: None,
         };
         s.registers.set_data(SUZYBUSEN, 1);
         s
@@ -111,6 +120,7 @@ impl Suzy {
 
     pub fn poke(&mut self, bus: &mut Bus) {
         assert!(bus.addr() >= SUZ_ADDR && bus.addr() <= (SUZ_ADDR | 0xff));
+        self.check_unsafe_access(bus);
         match bus.addr() {
             RCART0 => bus.set_status(BusStatus::PokeCart0),
             RCART1 => bus.set_status(BusStatus::PokeCart1),
@@ -162,6 +172,29 @@ impl Suzy {
         trace!("[{}] > Poke 0x{:04x} 0x{:02x}", self.ticks, bus.addr(), bus.data());
     }
 
+    /* The CPU only gets the bus during a sprite run when an interrupt wakes it up. Apart from SPRSYS, which
+    is how it asks the engine to stop, its writes to Suzy then race the sprite engine. */
+    fn check_unsafe_access(&mut self, bus: &Bus) {
+        if !self.registers.sprsys_r_is_flag_set(SprSysR::sprite_working) {
+            return;
+        }
+        let addr = bus.addr();
+        if !(TMPADRL..=SWITCHES).contains(&addr) || addr == SPRSYS {
+            return;
+        }
+        let access = UnsafeAccess {
+            tick: self.ticks,
+            addr,
+            data: bus.data(),
+            scb: self.registers.scb_addr(),
+        };
+        trace!("Unsafe access: 0x{:04x}=0x{:02x} while painting SCB 0x{:04x}.", access.addr, access.data, access.scb);
+        self.registers.sprsys_r_enable_flag(SprSysR::unsafe_acces);
+        if let Some(hook) = self.unsafe_access_hook.as_mut() {
+            hook.unsafe_access(access);
+        }
+    }
+
     fn has_bus(&self, bus: &mut Bus) -> bool {
         !bus.grant() 
     }
@@ -211,6 +244,13 @@ impl Suzy {
     }
 
     fn manage_task(&mut self, bus: &mut Bus, dma_ram: &mut Ram) {
+        /* The video DMA steals the bus from the sprite engine, which waits for it with its RAM page closed.
+        The math unit doesn't need the bus. */
+        if !self.has_bus(bus) && matches!(self.registers.task(), SuzyTask::SpriteGo | SuzyTask::EndSprite) {
+            self.renderer.close_dma_page(&mut self.registers);
+            return;
+        }
+
         if self.registers.task_ticks_delay() > 0 {
             self.registers.dec_task_ticks_delay();
             return;
@@ -277,7 +317,7 @@ impl Suzy {
                 bus.set_status(BusStatus::PokeDone);
             }           
             SuzyInstruction::Peek => { 
-                bus.set_data(self.registers.data(self.registers.addr_r())); 
+                bus.set_data(math::peek(&self.registers, self.registers.addr_r())); 
                 self.registers.reset_ir(); 
                 trace!("< Peek");
                 bus.set_status(BusStatus::PeekDone); 
@@ -331,8 +371,7 @@ impl Suzy {
                 if !self.has_bus(bus) {
                     return;
                 }
-                let mem_access_count = self.renderer.sprite_end(&mut self.registers, dma_ram); 
-                self.registers.set_task_ticks_delay(mem_access_count * RAM_PAGE_READ_TICKS as u16);
+                self.renderer.sprite_end(&mut self.registers, dma_ram); 
                 self.registers.set_task(SuzyTask::SpriteGo);  
             }             
             SuzyTask::Multiply => {
@@ -353,9 +392,39 @@ impl Suzy {
         &self.registers
     }    
 
+    pub fn fast_sprites(&self) -> bool {
+        self.renderer.fast()
+    }
+
+    pub fn set_fast_sprites(&mut self, fast: bool) {
+        self.renderer.set_fast(fast);
+    }
+
     pub fn left_handed(&self) -> bool {
         self.registers.sprsys_w_is_flag_set(SprSysW::left_handed)
     }
+
+    pub fn set_unsafe_access_hook(&mut self, hook: Option<Box<dyn UnsafeAccessHook>>) {
+        self.unsafe_access_hook = hook;
+    }
+
+    pub(crate) fn take_unsafe_access_hook(&mut self, other: &mut Suzy) {
+        self.unsafe_access_hook = other.unsafe_access_hook.take();
+    }
+}
+
+/* The hook isn't part of the state and stays with the original. */
+impl Clone for Suzy {
+    fn clone(&self) -> Self {
+        Self {
+            ticks: self.ticks,
+            request_monitor: self.request_monitor,
+            pending_bus_request_ticks: self.pending_bus_request_ticks,
+            renderer: self.renderer.clone(),
+            registers: self.registers.clone(),
+            unsafe_access_hook: None,
+        }
+    }
 }
 
 impl Default for Suzy {
@@ -396,3 +465,376 @@ impl Default for Suzy {
 
 
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{sync::Arc, vec::Vec};
+    use crate::cartridge::bs93_header::BS93_HEADER_LENGTH;
+    use crate::lynx::Lynx;
+    use parking_lot::Mutex;
+
+    const SCB_ADDR: u16 = 0x0280;
+    const DATA_ADDR: u16 = 0x0400;
+    const RESULT_ADDR: u16 = 0x0300;
+    // Timer 1 counts every 2us
+    const TIMER_TICKS: u64 = 32;
+
+    /* BS93 program drawing the sprite at SCB_ADDR while timer 1 counts down from 0xFF,
+    the count when the CPU wakes up is stored at RESULT_ADDR. */
+    fn timing_program(sprsys: u8, sprctl0: u8, sprcoll: u8, width: usize, height: usize) -> Vec<u8> {
+        let mut prg = vec![0x80, 0x08, 0x02, 0x00, 0x00, 0x00, b'B', b'S', b'9', b'3'];
+        let mut lda_sta = |v: u8, addr: u16| {
+            prg.extend_from_slice(&[0xA9, v, 0x8D, addr as u8, (addr >> 8) as u8]);
+        };
+        lda_sta(0x00, VIDBASL);
+        lda_sta(0x20, VIDBASH);
+        lda_sta(0x00, COLLBASL);
+        lda_sta(0x60, COLLBASH);
+        lda_sta(0x00, HOFFL);
+        lda_sta(0x00, VOFFL);
+        lda_sta(0x20, COLLOFFL);
+        lda_sta(0x7F, HSIZOFFL);
+        lda_sta(0x7F, VSIZOFFL);
+        lda_sta(0xF3, SPRINIT);
+        lda_sta(0x01, SUZYBUSEN);
+        lda_sta(sprsys, SPRSYS);
+        lda_sta(SCB_ADDR as u8, SCBNEXTL);
+        lda_sta((SCB_ADDR >> 8) as u8, SCBNEXTH);
+        lda_sta(0xFF, TIM1BKUP);
+        lda_sta(0xFF, TIM1CNT);
+        lda_sta(0x19, TIM1CTLA);
+        lda_sta(SPRGO_GO, SPRGO);
+        prg.extend_from_slice(&[
+            0x9C, CPUSLEEP as u8, (CPUSLEEP >> 8) as u8,
+            0xAD, TIM1CNT as u8, (TIM1CNT >> 8) as u8,
+            0x8D, RESULT_ADDR as u8, (RESULT_ADDR >> 8) as u8,
+            0x80, 0xFE,
+        ]);
+
+        prg.resize(BS93_HEADER_LENGTH + (SCB_ADDR - 0x0200) as usize, 0);
+        prg.extend_from_slice(&[
+            SPRCTL0_BPP | sprctl0, SPRCTL1_LITERAL | SPRCTL1_RELOAD_HV, sprcoll,
+            0x00, 0x00,
+            DATA_ADDR as u8, (DATA_ADDR >> 8) as u8,
+            0x10, 0x00, 0x10, 0x00,
+            0x00, 0x01, 0x00, 0x01,
+            0x01, 0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF,
+        ]);
+
+        prg.resize(BS93_HEADER_LENGTH + (DATA_ADDR - 0x0200) as usize, 0);
+        for _ in 0..height {
+            prg.push(1 + width as u8 / 2);
+            prg.extend(core::iter::repeat_n(0x11, width / 2));
+        }
+        prg.push(0);
+
+        let len = prg.len();
+        prg[4] = (len >> 8) as u8;
+        prg[5] = len as u8;
+        prg
+    }
+
+    /* Sprite drawing time measured by the program, in timer ticks. */
+    fn draw_time(sprsys: u8, sprctl0: u8, sprcoll: u8, width: usize, height: usize) -> u64 {
+        let mut lynx = Lynx::new();
+        lynx.load_bs93_direct(&timing_program(sprsys, sprctl0, sprcoll, width, height)).unwrap();
+        let screen = lynx.ram().get(0x2000 + 0x10 * 80 + 8);
+        while lynx.ram().get(RESULT_ADDR) == 0 {
+            assert!(lynx.ticks() < 100_000, "Sprite never finished.");
+            lynx.tick();
+        }
+        assert_ne!(lynx.ram().get(0x2000 + 0x10 * 80 + 8), screen);
+        (0xFF - lynx.ram().get(RESULT_ADDR)) as u64
+    }
+
+    fn assert_ticks(measured: u64, expected: u64) {
+        let ticks = measured * TIMER_TICKS;
+        assert!(ticks.abs_diff(expected) <= 2 * TIMER_TICKS, "measured {} ticks, expected {}", ticks, expected);
+    }
+
+    /* The expected times follow the documented RAM cycles and the access pattern the renderer models, they
+    check the model against itself. They were not measured on hardware, that is still to be done. */
+    #[test]
+    fn sprite_pixel_writes() {
+        let t8 = draw_time(SPRSYS_DONT_COLLIDE, 5, SPRCOLL_DONT_COLLIDE, 8, 8);
+        let t16 = draw_time(SPRSYS_DONT_COLLIDE, 5, SPRCOLL_DONT_COLLIDE, 16, 8);
+        /* A pixel is a write, every data byte is a normal read as the pixel writes closed its page.
+        "A page mode op-code read takes 4 ticks, a normal read or write to RAM takes 5 ticks." */
+        assert_ticks(t16 - t8, 8 * (8 * 5 + 4 * 5));
+    }
+
+    /* Same model as `sprite_pixel_writes`, not measured on hardware. */
+    #[test]
+    fn sprite_read_modify_write() {
+        let plain = draw_time(SPRSYS_DONT_COLLIDE, 5, SPRCOLL_DONT_COLLIDE, 16, 8);
+        /* The literal lines lose their last pixel, 15 are drawn out of 16.
+        XOR reads the byte it writes, in page mode for the second pixel of the byte. */
+        let xor = draw_time(SPRSYS_DONT_COLLIDE, 6, SPRCOLL_DONT_COLLIDE, 16, 8);
+        assert_ticks(xor - plain, 8 * (7 * (5 + 4) + 5));
+        /* The collision buffer is in another page, each pixel is a normal read and a write of it.
+        The result is written to the collision depository at the end of the sprite. */
+        let collide = draw_time(0, 4, 0x01, 16, 8);
+        assert_ticks(collide - plain, 8 * 15 * (5 + 5) + 5);
+    }
+
+    #[test]
+    fn fast_sprites_match_stepped() {
+        fn draw(prg: &[u8], fast: bool, display: bool) -> Lynx {
+            let mut lynx = Lynx::new();
+            lynx.set_fast_sprites(fast);
+            lynx.load_bs93_direct(prg).unwrap();
+            if display {
+                /* Line and frame timers as the boot ROM leaves them, the video DMA steals the bus in the
+                middle of the sprite lines. */
+                let mut cart = crate::cartridge::Cartridge::default();
+                for (addr, data) in crate::boot::BOOT_MIKEY_REGISTERS {
+                    lynx.mikey_mut().write(addr, data, &mut cart);
+                }
+            }
+            while lynx.ram().get(RESULT_ADDR) == 0 {
+                assert!(lynx.ticks() < 100_000, "Sprite never finished.");
+                lynx.tick();
+            }
+            lynx
+        }
+
+        for sprctl0 in 0..8 {
+            for (sprsys, sprcoll) in [(0, 0x03), (SPRSYS_DONT_COLLIDE, 0x03), (0, SPRCOLL_DONT_COLLIDE | 0x03)] {
+                /* The big sprites last for several display lines, the video DMA closes the sprite engine's
+                page in the middle of the pixel lines the fast mode renders at once. */
+                for (width, height, display) in [(8, 8, false), (16, 3, false), (2, 20, false), (64, 64, true), (2, 48, true)] {
+                    let prg = timing_program(sprsys, sprctl0, sprcoll, width, height);
+                    let stepped = draw(&prg, false, display);
+                    let fast = draw(&prg, true, display);
+                    assert!(fast.fast_sprites());
+                    assert_eq!(stepped.ticks(), fast.ticks(), "sprctl0 {} sprsys {} sprcoll {} {}x{}", sprctl0, sprsys, sprcoll, width, height);
+                    assert!((0..=0xFFFF).all(|a| stepped.ram().get(a) == fast.ram().get(a)));
+                }
+            }
+        }
+    }
+
+    fn poke(suzy: &mut Suzy, bus: &mut Bus, addr: u16, data: u8) {
+        bus.set_addr(addr);
+        bus.set_data(data);
+        suzy.poke(bus);
+        while bus.status() != BusStatus::PokeDone {
+            suzy.tick(bus, &mut Ram::new());
+        }
+        bus.set_status(BusStatus::None);
+    }
+
+    fn peek(suzy: &mut Suzy, bus: &mut Bus, addr: u16) -> u8 {
+        bus.set_addr(addr);
+        suzy.peek(bus);
+        while bus.status() != BusStatus::PeekDone {
+            suzy.tick(bus, &mut Ram::new());
+        }
+        bus.set_status(BusStatus::None);
+        bus.data()
+    }
+
+    fn math_working(suzy: &Suzy) -> bool {
+        suzy.registers.sprsys() & SPRSYS_MATH_IN_PROGRESS != 0
+    }
+
+    #[test]
+    fn math_latency() {
+        let mut suzy = Suzy::new();
+        let mut bus = Bus::new();
+        let mut ram = Ram::new();
+        for (sprsys, start, ticks) in [
+            (0, MATHA, SUZY_MULT_NON_SIGN_TICKS),
+            (SPRSYS_SIGN_MATH, MATHA, SUZY_MULT_SIGN_TICKS),
+            (SPRSYS_ACCUMULATE, MATHA, SUZY_MULT_SIGN_TICKS),
+            (0, MATHE, 176 + 14 * 8),
+        ] {
+            poke(&mut suzy, &mut bus, SPRSYS, sprsys);
+            poke(&mut suzy, &mut bus, MATHP, 0x80);
+            poke(&mut suzy, &mut bus, MATHN, 0x00);
+            poke(&mut suzy, &mut bus, start, 0x12);
+            let mut working = 0;
+            while math_working(&suzy) {
+                working += 1;
+                suzy.tick(&mut bus, &mut ram);
+            }
+            assert_eq!(working, ticks);
+        }
+    }
+
+    #[test]
+    fn math_in_progress() {
+        let mut suzy = Suzy::new();
+        let mut bus = Bus::new();
+        let mut ram = Ram::new();
+
+        // 0xFFFF * 0x0003
+        poke(&mut suzy, &mut bus, MATHD, 0x03);
+        poke(&mut suzy, &mut bus, MATHC, 0x00);
+        poke(&mut suzy, &mut bus, MATHB, 0xFF);
+        poke(&mut suzy, &mut bus, MATHA, 0xFF);
+        let mut products = vec![];
+        while math_working(&suzy) {
+            products.push(u32::from_le_bytes([MATHH, MATHG, MATHF, MATHE].map(|addr| math::peek(&suzy.registers, addr))));
+            suzy.tick(&mut bus, &mut ram);
+        }
+        /* The 44 ticks multiply adds 0x0003 shifted by one more bit of 0xFFFF every 45 / 16 ticks,
+        the write of A being the first tick. */
+        assert_eq!(products.len(), 44);
+        assert_eq!(products[1], 0);
+        assert_eq!(products[2], 0x0003);
+        assert_eq!(products[11], 0x000F * 3);
+        assert_eq!(products[22], 0x00FF * 3);
+        assert_eq!(products[33], 0x0FFF * 3);
+        assert_eq!(products[43], 0x7FFF * 3);
+        assert_eq!(suzy.registers.efgh(), 0xFFFF * 3);
+
+        // 0x12345678 / 0x0009, the quotient is built from its most significant bit
+        for (addr, v) in [(MATHP, 0x09), (MATHN, 0x00), (MATHH, 0x78), (MATHG, 0x56), (MATHF, 0x34), (MATHE, 0x12)] {
+            poke(&mut suzy, &mut bus, addr, v);
+        }
+        while suzy.registers.task_ticks_delay() > suzy.registers.math_ticks() / 2 {
+            suzy.tick(&mut bus, &mut ram);
+        }
+        let a = peek(&mut suzy, &mut bus, MATHA);
+        let b = peek(&mut suzy, &mut bus, MATHB);
+        let d = peek(&mut suzy, &mut bus, MATHD);
+        assert!(math_working(&suzy));
+        assert_eq!((a, b, d), (((0x12345678 / 9) >> 24) as u8, ((0x12345678 / 9) >> 16) as u8, 0));
+        while math_working(&suzy) {
+            suzy.tick(&mut bus, &mut ram);
+        }
+        assert_eq!(peek(&mut suzy, &mut bus, MATHD), (0x12345678_u32 / 9) as u8);
+        assert_eq!(peek(&mut suzy, &mut bus, MATHM), (0x12345678_u32 % 9) as u8);
+    }
+
+    #[test]
+    fn video_dma_steals_bus() {
+        fn draw(steal_every: Option<u64>) -> (u64, u64) {
+            let mut lynx = Lynx::new();
+            lynx.load_bs93_direct(&timing_program(SPRSYS_DONT_COLLIDE, 5, SPRCOLL_DONT_COLLIDE, 16, 8)).unwrap();
+            let mut ram = lynx.ram().clone();
+            let mut suzy = Suzy::new();
+            let mut bus = Bus::new();
+            suzy.registers.set_u16(VIDBASL, 0x2000);
+            suzy.registers.set_u16(COLLBASL, 0x6000);
+            suzy.registers.set_u16(HSIZOFFL, 0x7F);
+            suzy.registers.set_u16(VSIZOFFL, 0x7F);
+            suzy.registers.set_sprsys(SPRSYS_DONT_COLLIDE);
+            suzy.registers.set_u16(SCBNEXTL, SCB_ADDR);
+            suzy.registers.set_data(SPRGO, SPRGO_GO);
+            bus.set_grant(false);
+
+            let mut ticks = 0;
+            let mut video_dma: Option<u16> = None;
+            while suzy.registers.data(SPRGO) & SPRGO_GO != 0 {
+                ticks += 1;
+                assert!(ticks < 100_000, "Sprite never finished.");
+                match video_dma {
+                    None if steal_every.is_some_and(|n| ticks % n == 0) => {
+                        bus.set_request(true);
+                        video_dma = Some(REFRESH_AND_VIDEO_DMA_TICKS);
+                    }
+                    Some(0) => {
+                        bus.set_grant(false);
+                        video_dma = None;
+                    }
+                    Some(t) if bus.grant() => video_dma = Some(t - 1),
+                    _ => (),
+                }
+                suzy.tick(&mut bus, &mut ram);
+            }
+            (ticks, suzy.renderer.dma_ticks())
+        }
+
+        let (ticks, dma_ticks) = draw(None);
+        let (stolen_ticks, stolen_dma_ticks) = draw(Some(200));
+        let steals = stolen_ticks / 200;
+        assert!(steals > 2);
+        /* Suzy waits for the DMA, then reopens the page of the sprite data: a normal read of 5 ticks
+        instead of a page mode read of 4. */
+        assert!(stolen_ticks >= ticks + steals * (SUZY_BUS_GRANT_TICKS + REFRESH_AND_VIDEO_DMA_TICKS) as u64);
+        assert!(stolen_dma_ticks >= dma_ticks);
+        assert!(stolen_dma_ticks <= dma_ticks + steals);
+    }
+
+    /* Suzy in the middle of the first of two chained copies of the timing program sprite,
+    the second is drawn 0x20 lines lower. */
+    fn sprite_chain() -> (Suzy, Bus, Ram) {
+        let mut lynx = Lynx::new();
+        lynx.load_bs93_direct(&timing_program(SPRSYS_DONT_COLLIDE, 5, SPRCOLL_DONT_COLLIDE, 16, 8)).unwrap();
+        let mut ram = lynx.ram().clone();
+        for i in 0..23 {
+            ram.set(SCB_ADDR + 0x40 + i, ram.get(SCB_ADDR + i));
+        }
+        ram.set(SCB_ADDR + 3, (SCB_ADDR + 0x40) as u8);
+        ram.set(SCB_ADDR + 4, ((SCB_ADDR + 0x40) >> 8) as u8);
+        ram.set(SCB_ADDR + 0x40 + 9, 0x30);
+
+        let mut suzy = Suzy::new();
+        let mut bus = Bus::new();
+        suzy.registers.set_u16(VIDBASL, 0x2000);
+        suzy.registers.set_u16(HSIZOFFL, 0x7F);
+        suzy.registers.set_u16(VSIZOFFL, 0x7F);
+        suzy.registers.set_sprsys(SPRSYS_DONT_COLLIDE);
+        suzy.registers.set_u16(SCBNEXTL, SCB_ADDR);
+        suzy.registers.set_data(SPRGO, SPRGO_GO);
+        bus.set_grant(false);
+        for _ in 0..200 {
+            suzy.tick(&mut bus, &mut ram);
+        }
+        assert_eq!(suzy.registers.scb_addr(), SCB_ADDR);
+        assert!(suzy.registers.sprsys() & SPRSYS_SPRITE_IN_PROGRESS != 0);
+        /* An interrupt woke the CPU up. */
+        bus.set_grant(true);
+        (suzy, bus, ram)
+    }
+
+    fn finish_sprites(suzy: &mut Suzy, bus: &mut Bus, ram: &mut Ram) {
+        bus.set_grant(false);
+        while suzy.registers.data(SPRGO) & SPRGO_GO != 0 {
+            assert!(suzy.ticks < 100_000, "Sprite never finished.");
+            suzy.tick(bus, ram);
+        }
+    }
+
+    #[test]
+    fn unsafe_access() {
+        let (mut suzy, mut bus, mut ram) = sprite_chain();
+        let accesses = Arc::new(Mutex::new(Vec::new()));
+        let hook_accesses = accesses.clone();
+        suzy.set_unsafe_access_hook(Some(Box::new(move |access: UnsafeAccess| hook_accesses.lock().push(access))));
+
+        poke(&mut suzy, &mut bus, SPRSYS, SPRSYS_DONT_COLLIDE);
+        assert_eq!(peek(&mut suzy, &mut bus, SPRSYS) & SPRSYS_UNSAFE_ACCESS, 0);
+        let tick = suzy.ticks;
+        poke(&mut suzy, &mut bus, MATHD, 0x34);
+        assert_ne!(peek(&mut suzy, &mut bus, SPRSYS) & SPRSYS_UNSAFE_ACCESS, 0);
+        assert_eq!(accesses.lock()[..], [UnsafeAccess { tick, addr: MATHD, data: 0x34, scb: SCB_ADDR }]);
+
+        /* The flag stays set after the sprites until cleared. */
+        finish_sprites(&mut suzy, &mut bus, &mut ram);
+        assert_ne!(peek(&mut suzy, &mut bus, SPRSYS) & SPRSYS_UNSAFE_ACCESS, 0);
+        poke(&mut suzy, &mut bus, SPRSYS, SPRSYS_DONT_COLLIDE | SPRSYS_CLEAR_UNSAFE);
+        assert_eq!(peek(&mut suzy, &mut bus, SPRSYS) & SPRSYS_UNSAFE_ACCESS, 0);
+        poke(&mut suzy, &mut bus, MATHD, 0x34);
+        assert_eq!(accesses.lock().len(), 1);
+    }
+
+    #[test]
+    fn sprite_to_stop() {
+        for stop in [false, true] {
+            let (mut suzy, mut bus, mut ram) = sprite_chain();
+            let sprsys = if stop { SPRSYS_DONT_COLLIDE | SPRSYS_STOP_CURRENT_SPRITE } else { SPRSYS_DONT_COLLIDE };
+            poke(&mut suzy, &mut bus, SPRSYS, sprsys);
+            assert_eq!(peek(&mut suzy, &mut bus, SPRSYS) & SPRSYS_STOP_CURRENT_SPRITE, sprsys & SPRSYS_STOP_CURRENT_SPRITE);
+            finish_sprites(&mut suzy, &mut bus, &mut ram);
+
+            /* The current sprite is finished, the next one isn't drawn. */
+            assert_eq!(ram.get(0x2000 + 0x17 * 80 + 8), 0x11);
+            assert_eq!(ram.get(0x2000 + 0x30 * 80 + 8) == 0x11, !stop);
+            assert_eq!(peek(&mut suzy, &mut bus, SPRSYS) & (SPRSYS_SPRITE_IN_PROGRESS | SPRSYS_STOP_CURRENT_SPRITE | SPRSYS_UNSAFE_ACCESS), 0);
+            assert!(suzy.registers.task() == SuzyTask::None);
+        }
+    }
+}
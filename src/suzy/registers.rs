@@ -33,7 +33,7 @@ bitflags! {
 }
 
 bitflags! {
-    #[derive(Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
     pub struct Joystick:u8
     {
         const up       = 0b01000000;
@@ -48,7 +48,7 @@ bitflags! {
 }
 
 bitflags! {
-    #[derive(Copy, Clone, PartialEq, Serialize, Deserialize)]
+    #[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
     pub struct Switches:u8
     {
         const cart1_inactive = 0b00000100;
@@ -97,11 +97,12 @@ pub fn joystick_swap(mut j: Joystick, b1: Joystick, b2: Joystick) -> Joystick {
     j
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct SuzyRegisters {
     data: Vec<u8>,
     ir_ticks_delay: u16,
     task_ticks_delay: u16,
+    math_ticks: u16,
     sprsys_r: SprSysR,
     sprsys_w: SprSysW,
     sign_ab: i8,
@@ -121,6 +122,7 @@ impl SuzyRegisters {
             data: vec![0; 0x100],      
             ir_ticks_delay: 0,      
             task_ticks_delay: 0,     
+            math_ticks: 0,
             sprsys_r: SprSysR::empty(),
             sprsys_w: SprSysW::empty(),
             sign_ab: 0,
@@ -287,15 +289,9 @@ impl SuzyRegisters {
             Some(bits) => bits,
             None => SprSysW::empty()
         };
-        if self.sprsys_w_is_flag_set(SprSysW::v_stretching) {
-            self.sprsys_r_enable_flag(SprSysR::v_stretching)
-        }
-        if self.sprsys_w_is_flag_set(SprSysW::left_handed) {
-            self.sprsys_r_enable_flag(SprSysR::left_handed)
-        }
-        if self.sprsys_w_is_flag_set(SprSysW::sprite_to_stop) {
-            self.sprsys_r_enable_flag(SprSysR::sprite_to_stop)
-        }
+        self.sprsys_r.set(SprSysR::v_stretching, self.sprsys_w_is_flag_set(SprSysW::v_stretching));
+        self.sprsys_r.set(SprSysR::left_handed, self.sprsys_w_is_flag_set(SprSysW::left_handed));
+        self.sprsys_r.set(SprSysR::sprite_to_stop, self.sprsys_w_is_flag_set(SprSysW::sprite_to_stop));
         if self.sprsys_w_is_flag_set(SprSysW::clear_unsafe) {
             self.sprsys_r_disable_flag(SprSysR::unsafe_acces)
         }
@@ -428,6 +424,17 @@ impl SuzyRegisters {
         self.task_ticks_delay += ticks_delay;
     }
 
+    /* Duration of the current math operation. */
+    #[inline]
+    pub fn math_ticks(&self) -> u16 {
+        self.math_ticks
+    }
+
+    #[inline]
+    pub fn set_math_ticks(&mut self, ticks: u16) {
+        self.math_ticks = ticks;
+    }
+
     #[inline]
     pub fn dec_task_ticks_delay(&mut self) {
         self.task_ticks_delay -= 1;
@@ -547,16 +554,11 @@ impl Default for SuzyRegisters {
 mod tests {
     use super::*;
 
+    #[derive(Default)]
     struct TestCore {
         regs: SuzyRegisters,
     }
 
-    impl Default for TestCore {
-        fn default() -> Self {
-            Self { regs: SuzyRegisters::new() }
-        }
-    }
-
     macro_rules! T {
         ($b: expr) => {
             assert!($b);
@@ -856,6 +858,76 @@ mod tests {
         DIV_T!(m, 65535_u32, 65535_u32);
     }
 
+    /* Hardware quirks of the math unit listed in the hardware documentation. The results are the ones
+    Handy gives (susie.cpp, the MATHA and MATHC writes), each differs from plain two's complement math.
+    They were not checked on hardware.
+    TODO: run these vectors on a Lynx and replace the expected values with the measured ones. */
+    #[test]
+    fn quirks() {
+        // "In signed multiply, the hardware thinks that 8000 is a positive number."
+        // (ab, cd, efgh, two's complement efgh)
+        const MULT: [(u16, u16, u32, u32); 3] = [
+            (0x8000, 0x0002, 0x0001_0000, 0xFFFF_0000),
+            (0x8000, 0xFFFF, 0xFFFF_8000, 0x0000_8000),
+            (0x0003, 0x8000, 0x0001_8000, 0xFFFE_8000),
+        ];
+        for (ab, cd, efgh, twos) in MULT {
+            let mut m: TestCore = TestCore::default();
+            SIGNED!(m);
+            MULT_T!(m, ab, cd, efgh);
+            T!(efgh != twos);
+        }
+
+        // "In signed multiply, the hardware thinks that 0 is a negative number. [...] you can not depend on
+        // the sign flag to be correct if you just load the lower byte after a multiply by zero."
+        // CD is 0 when C is written, the sign is kept when only D is loaded afterwards: 2 * 3 is -6.
+        let mut m: TestCore = TestCore::default();
+        SIGNED!(m);
+        MULT_T!(m, 0x0002, 0x0000, 0);
+        SD!(m, 0x03);
+        SAB!(m, 0x0002);
+        MULT!(m);
+        assert_eq!(EFGH!(m), 0xFFFF_FFFA);
+        // Loading C again converts the sign.
+        SCD!(m, 0x0003);
+        SAB!(m, 0x0002);
+        MULT!(m);
+        assert_eq!(EFGH!(m), 6);
+
+        // The divide is unsigned, whatever the sign flag.
+        // (efgh, np, abcd, jklm), -16 / 16, -1 / 16 and -2^31 / -1 in two's complement.
+        const DIV: [(u32, u16, u32, u32); 3] = [
+            (0xFFFF_FFF0, 0x0010, 0x0FFF_FFFF, 0),
+            (0xFFFF_FFFF, 0x0010, 0x0FFF_FFFF, 0x000F),
+            (0x8000_0000, 0xFFFF, 0x0000_8000, 0x8000),
+        ];
+        for (efgh, np, abcd, jklm) in DIV {
+            let mut m: TestCore = TestCore::default();
+            SIGNED!(m);
+            SEFGH!(m, efgh);
+            SNP!(m, np);
+            DIV!(m);
+            assert_eq!((ABCD!(m), JKLM!(m)), (abcd, jklm));
+            T!(!CY!(m) && !WN!(m));
+        }
+    }
+
+    #[test]
+    fn accumulate_overflow() {
+        let mut m: TestCore = TestCore::default();
+        ACC!(m);
+        SJKLM!(m, 0xFFFF_0000_u32);
+        // The accumulator wraps around and reports the carry out of bit 31.
+        MULT_T!(m, 0x1000, 0x1000, 0x0100_0000);    TJKLM!(m, 0x00FF_0000, true, true);
+        // "The write to 'M' will clear the accumulator overflow bit"
+        SM!(m, 0);
+        T!(!WN!(m));
+        T!(JKLM!(m) == 0x00FF_0000);
+        // A signed product is accumulated as its 32 bits two's complement.
+        SIGNED!(m);
+        MULT_T!(m, 0xFFFF, 0x0001, 0xFFFF_FFFF);    TJKLM!(m, 0x00FE_FFFF, true, true);
+    }
+
     #[test]
     fn div_0() {
         let mut m: TestCore = TestCore::default();
@@ -1,19 +1,21 @@
+use alloc::vec::Vec;
 use log::trace;
 use mikey::video::{LYNX_SCREEN_HEIGHT, LYNX_SCREEN_WIDTH};
 use suzy::*;
 use sprite_data::SpriteData;
+use dma::SuzyDma;
 use crate::*;
 
 macro_rules! peek_dma {
-    ($regs: ident, $ram: ident, $addr: expr) => {{
-        $regs.set_task_ticks_delay(RAM_PAGE_READ_TICKS as u16);
-        $ram.get($addr)
+    ($slf: ident, $ram: ident, $addr: expr) => {{
+        let addr = $addr;
+        $slf.dma.peek($ram, addr)
     }};
 }
 
 macro_rules! peek_scb_header {
     ($slf: ident, $regs: ident, $ram: ident) => {{
-        let data = peek_dma!($regs, $ram, $slf.sprite_data.addr());
+        let data = peek_dma!($slf, $ram, $slf.sprite_data.addr());
         $slf.inc_scb_curr_adr();
         $slf.scb_step += 1;
         data
@@ -22,7 +24,7 @@ macro_rules! peek_scb_header {
 
 macro_rules! peek_and_store_scb_data {
     ($slf: ident, $regs: ident, $ram: ident) => {
-        let data = peek_dma!($regs, $ram, $slf.sprite_data.addr());        
+        let data = peek_dma!($slf, $ram, $slf.sprite_data.addr());        
         $slf.sprite_data.push_data(data);
         $slf.inc_scb_curr_adr();
     };
@@ -35,7 +37,7 @@ macro_rules! peek_and_store_scb_header {
     }};
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Renderer {
     scb_step: u8,
     scb_pen_idx: usize,
@@ -58,7 +60,13 @@ pub struct Renderer {
     pixel: u32,
     pixel_width: u8,
     collision: u8,
-    pens: [u8; 16]
+    pens: [u8; 16],
+    dma: SuzyDma,
+    fast: bool,
+    /* Steps of the current fast batch: tick they would have started at in stepped mode and whether their
+    first RAM access was a page mode read. */
+    batch: Vec<(u16, Option<bool>)>,
+    batch_delay: u16,
 }
 
 impl Renderer {
@@ -85,7 +93,11 @@ impl Renderer {
             pixel: 0,
             pixel_width: 0,
             collision: 0,
-            pens: [0; 16]
+            pens: [0; 16],
+            dma: SuzyDma::new(),
+            fast: false,
+            batch: Vec::new(),
+            batch_delay: 0,
         }
     }
 
@@ -112,7 +124,6 @@ impl Renderer {
         } else {
             self.sprite_data.reset(regs);
             self.sprite_data.set_addr(scbaddr);
-            regs.set_task_ticks_delay(SUZY_SPRITE_SCB_ADDITIONAL_COST);
             regs.inc_task_step();
         }
     }
@@ -121,6 +132,8 @@ impl Renderer {
         self.scb_step = 0;
         regs.sprsys_r_disable_flag(SprSysR::sprite_working);
         regs.sprsys_r_disable_flag(SprSysR::math_working);
+        regs.sprsys_r_disable_flag(SprSysR::sprite_to_stop);
+        regs.sprsys_w_disable_flag(SprSysW::sprite_to_stop);
         regs.reset_task();
     }
 
@@ -139,7 +152,7 @@ impl Renderer {
                     trace!("Sprite skipped.");
                     self.scb_step = 0;
                     self.sprite_data.reset(regs);
-                    regs.set_task_step(TaskStep::InitializePainting); // next scb if any                    
+                    self.next_scb(regs);
                 }
             }
             5 => peek_and_store_scb_header!(self, regs, ram, SPRDLINEL),
@@ -224,7 +237,7 @@ impl Renderer {
         self.screen_v_start = self.voff;
         self.orig_hsign = if self.start_quadrant == 0 || self.start_quadrant == 1 {1} else {-1};
         self.orig_vsign = if self.start_quadrant == 0 || self.start_quadrant == 3 {1} else {-1};
-        
+
         regs.inc_task_step();
     }
 
@@ -279,14 +292,12 @@ impl Renderer {
         }
     }
 
-    pub fn sprite_end(&mut self, regs: &mut SuzyRegisters, ram: &mut Ram) -> u16 {
-        let mut mem_count = 0;
+    pub fn sprite_end(&mut self, regs: &mut SuzyRegisters, ram: &mut Ram) {
         if regs.sprcoll() & SPRCOLL_DONT_COLLIDE == 0 && !regs.sprsys_w_is_flag_set(SprSysW::no_collide) {
             match regs.sprctl0() & SPRCTL0_SPR_TYPE {
                 2 | 3 | 4 | 6 | 7 => {
                     let coladr = regs.scb_addr().overflowing_add(regs.u16(COLLOFFL)).0;
-                    ram.set(coladr, self.collision);
-                    mem_count += 1;
+                    self.dma.poke(ram, coladr, self.collision);
                     trace!("set collision 0x{:04X}=0x{:02X}", coladr, self.collision);
                 }
                 _ => (),
@@ -295,21 +306,28 @@ impl Renderer {
 
         if regs.data(SPRGO) & SPRGO_EVERON != 0 {
             let coladr = regs.scb_addr().overflowing_add(regs.u16(COLLOFFL)).0;
-            let mut coldat = ram.get(coladr);
+            let mut coldat = self.dma.peek(ram, coladr);
             if !self.ever_on_screen {
                 coldat |= 0x80;
             } else {
                 coldat &= 0x7f;
             }
-            ram.set(coladr, coldat);
+            self.dma.poke(ram, coladr, coldat);
         }
 
+        self.next_scb(regs);
+        self.batch.clear();
+        self.apply_dma_ticks(regs);
+    }
+
+    /* "Sprite to stop" lets the current SCB finish, then the engine stops instead of following the chain. */
+    fn next_scb(&mut self, regs: &mut SuzyRegisters) {
         if regs.sprsys_w_is_flag_set(SprSysW::sprite_to_stop) {
-            regs.inc_task_step();
+            trace!("Sprite engine stopped after SCB 0x{:04X}.", regs.scb_addr());
+            regs.set_task_step(TaskStep::MaxSteps);
         } else {
             regs.set_task_step(TaskStep::InitializePainting); // next scb if any
         }
-        mem_count
     }
 
     fn render_lines_start(&mut self, ram: &mut Ram, regs: &mut SuzyRegisters) {
@@ -397,7 +415,7 @@ impl Renderer {
         if self.hsign != self.hquadoff {
             self.hoff += self.hsign;
         }
-                
+
         regs.inc_task_step();
     }
 
@@ -423,7 +441,6 @@ impl Renderer {
         else {            
             self.sprite_data.reset(regs);
             peek_and_store_scb_data!(self, regs, ram);
-            regs.add_task_ticks_delay(SUZY_SPRITE_VERT_ADDITIONAL_COST);
             regs.set_task_step(TaskStep::RenderPixelHeightStart); 
         }
     }
@@ -436,8 +453,6 @@ impl Renderer {
             return;
         }
 
-        let mut mem_access_count: u16 = 0;
-
         match self.sprite_data.line_get_pixel(regs, &self.pens) {
             Result::Err(_e) => { 
                 peek_and_store_scb_data!(self, regs, ram);
@@ -458,16 +473,14 @@ impl Renderer {
         for _ in 0..self.pixel_width {
             if self.hoff >= 0 && self.hoff < LYNX_SCREEN_WIDTH as i16 {
                 self.ever_on_screen = true;                
-                mem_access_count += self.process_pixel(regs, ram); 
+                self.process_pixel(regs, ram);
                 trace!("- RenderPixel. width:{}", self.pixel_width);    
             }
             self.hoff += self.hsign;
         }
-
-        regs.set_task_ticks_delay(mem_access_count);
     }
 
-    fn write_pixel(&mut self, regs: &SuzyRegisters, ram: &mut Ram, pixel: u32) -> u16 {
+    fn write_pixel(&mut self, regs: &SuzyRegisters, ram: &mut Ram, pixel: u32) {
         let scr_addr : u16 = regs.u16(VIDADRL) + (self.hoff as u16 / 2);
 
         let mut dest: u8 = ram.get(scr_addr);
@@ -479,16 +492,14 @@ impl Renderer {
             dest &= 0xf0;
             dest |= pixel as u8;
         }
-        ram.set(scr_addr, dest);
+        self.dma.poke(ram, scr_addr, dest);
         trace!("write_pixel({}, {}) 0x{:04x} = 0x{:02x}", self.hoff, pixel, scr_addr, dest);
-
-        2
     }
 
-    fn read_pixel(&mut self, regs: &SuzyRegisters, ram: &mut Ram) -> (u8, u16) {
+    fn read_pixel(&mut self, regs: &SuzyRegisters, ram: &mut Ram) -> u8 {
         let scr_addr : u16 = regs.u16(VIDADRL) + (self.hoff as u16 / 2);
 
-        let mut data: u8 = ram.get(scr_addr);
+        let mut data: u8 = self.dma.peek(ram, scr_addr);
 
         if self.hoff & 0x01 == 0 {
             data >>= 4;
@@ -496,10 +507,10 @@ impl Renderer {
             data &= 0x0f;
         }
 
-        (data, 1)
+        data
     }
 
-    fn write_collision(&mut self, regs: &SuzyRegisters, ram: &mut Ram, pixel: u8) -> u16 {
+    fn write_collision(&mut self, regs: &SuzyRegisters, ram: &mut Ram, pixel: u8) {
         let col_addr = regs.u16(COLLADRL) + (self.hoff as u16 / 2);
 
         let mut dest: u8 = ram.get(col_addr);
@@ -512,15 +523,14 @@ impl Renderer {
             dest &= 0xf0;
             dest |= pixel;
         }
-        ram.set(col_addr, dest);
+        self.dma.poke(ram, col_addr, dest);
         trace!("Write collision pixel 0x{:04x} = 0x{:02x}", col_addr, dest);
-        2
     }
 
-    fn read_collision(&mut self, regs: &SuzyRegisters, ram: &mut Ram) -> (u8, u16) {
+    fn read_collision(&mut self, regs: &SuzyRegisters, ram: &mut Ram) -> u8 {
         let col_addr : u16 = regs.u16(COLLADRL) + (self.hoff as u16 / 2);
 
-        let mut data: u8 = ram.get(col_addr);
+        let mut data: u8 = self.dma.peek(ram, col_addr);
 
         if self.hoff & 0x01 == 0 {
             data >>= 4;
@@ -528,12 +538,10 @@ impl Renderer {
             data &= 0x0f;
         }
 
-        (data, 1)
+        data
     }
 
-    pub fn process_pixel(&mut self, regs: &mut SuzyRegisters, ram: &mut Ram) -> u16 {
-        let mut mem_accesses : u16 = 0;
-
+    pub fn process_pixel(&mut self, regs: &mut SuzyRegisters, ram: &mut Ram) {
         trace!("process_pixel() 0x{:04x} 0x{:02x} type:{}", self.hoff, self.pixel, regs.sprctl0() & SPRCTL0_SPR_TYPE);
 
         let sprcoll = regs.sprcoll();
@@ -541,103 +549,122 @@ impl Renderer {
         match regs.sprctl0() & SPRCTL0_SPR_TYPE {
             // 0 - BACKGROUND SHADOW
             0 => { 
-                mem_accesses += self.write_pixel(regs, ram, self.pixel);
+                self.write_pixel(regs, ram, self.pixel);
                 if sprcoll & SPRCOLL_DONT_COLLIDE == 0 && !regs.sprsys_w_is_flag_set(SprSysW::no_collide) && self.pixel != 0x0e {
-                    mem_accesses += self.write_collision(regs, ram, sprcoll & SPRCOLL_NUMBER);
+                    self.write_collision(regs, ram, sprcoll & SPRCOLL_NUMBER);
                 }
             }
 
             // 1 - BACKGROUND NOCOLLIDE
-            1 => mem_accesses += self.write_pixel(regs, ram, self.pixel),
+            1 => self.write_pixel(regs, ram, self.pixel),
 
             // 2 - BOUNDARY_SHADOW
             2 => {
                 if self. pixel != 0x00 && self.pixel != 0x0e && self.pixel != 0x0f {
-                    mem_accesses += self.write_pixel(regs, ram, self.pixel);
+                    self.write_pixel(regs, ram, self.pixel);
                 }
 
                 if self.pixel != 0x00 && self.pixel != 0x0e && sprcoll & SPRCOLL_DONT_COLLIDE == 0 && !regs.sprsys_w_is_flag_set(SprSysW::no_collide) {
-                    let (c, m) = self.read_collision(regs, ram);
-                    mem_accesses += m;
+                    let c = self.read_collision(regs, ram);
                     if c > self.collision {
                         self.collision = c;
                     }
-                    mem_accesses += self.write_collision(regs, ram, sprcoll & SPRCOLL_NUMBER);
+                    self.write_collision(regs, ram, sprcoll & SPRCOLL_NUMBER);
                 }
             }
 
             // 3 - BOUNDARY
             3 => {
                 if self.pixel != 0x00 && self.pixel != 0x0f {
-                    mem_accesses += self.write_pixel(regs, ram, self.pixel);
+                    self.write_pixel(regs, ram, self.pixel);
                 }
                 if self.pixel != 0x00 && sprcoll & SPRCOLL_DONT_COLLIDE == 0 && !regs.sprsys_w_is_flag_set(SprSysW::no_collide) {
-                    let (c, m) = self.read_collision(regs, ram);
-                    mem_accesses += m;
+                    let c = self.read_collision(regs, ram);
                     if c > self.collision {
                         self.collision = c;
                     }
-                    mem_accesses += self.write_collision(regs, ram, sprcoll & SPRCOLL_NUMBER);
+                    self.write_collision(regs, ram, sprcoll & SPRCOLL_NUMBER);
                 }
             }
 
             // 4 - NORMAL
-            4 => {
-                if self.pixel != 0x00 {
-                    mem_accesses += self.write_pixel(regs, ram, self.pixel);
-                    if sprcoll & SPRCOLL_DONT_COLLIDE == 0 && !regs.sprsys_w_is_flag_set(SprSysW::no_collide) {
-                        let (c, m) = self.read_collision(regs, ram);
-                        mem_accesses += m;
-                        if c > self.collision {
-                            self.collision = c;
-                        }
-                        mem_accesses += self.write_collision(regs, ram, sprcoll & SPRCOLL_NUMBER);
+            4 if self.pixel != 0x00 => {
+                self.write_pixel(regs, ram, self.pixel);
+                if sprcoll & SPRCOLL_DONT_COLLIDE == 0 && !regs.sprsys_w_is_flag_set(SprSysW::no_collide) {
+                    let c = self.read_collision(regs, ram);
+                    if c > self.collision {
+                        self.collision = c;
                     }
+                    self.write_collision(regs, ram, sprcoll & SPRCOLL_NUMBER);
                 }
             }
 
             // 5 - NOCOLLIDE
-            5 => if self.pixel != 0x00 { mem_accesses += self.write_pixel(regs, ram, self.pixel); },
+            5 if self.pixel != 0x00 => self.write_pixel(regs, ram, self.pixel),
             
             // 6 - XOR SHADOW
             6 => {
                 if self.pixel != 0x00 {
-                    let (p, m) = self.read_pixel(regs, ram);
-                    mem_accesses += m;
-                    mem_accesses += self.write_pixel(regs, ram, p as u32 ^ self.pixel);
+                    let p = self.read_pixel(regs, ram);
+                    self.write_pixel(regs, ram, p as u32 ^ self.pixel);
                 }
                 if self.pixel != 0x00 && self.pixel != 0x0e && sprcoll & SPRCOLL_DONT_COLLIDE == 0 && !regs.sprsys_w_is_flag_set(SprSysW::no_collide) {
-                    let (c, m) = self.read_collision(regs, ram);
-                    mem_accesses += m;
+                    let c = self.read_collision(regs, ram);
                     if c > self.collision {
                         self.collision = c;
                     }
-                    mem_accesses += self.write_collision(regs, ram, sprcoll & SPRCOLL_NUMBER);
+                    self.write_collision(regs, ram, sprcoll & SPRCOLL_NUMBER);
                 }
             }
 
             // 7 - SHADOW
             7 => {
                 if self.pixel != 0x00 {
-                    mem_accesses += self.write_pixel(regs, ram, self.pixel);
+                    self.write_pixel(regs, ram, self.pixel);
                 }
                 if self.pixel != 0x00 && self.pixel != 0x0e && sprcoll & SPRCOLL_DONT_COLLIDE == 0 && !regs.sprsys_w_is_flag_set(SprSysW::no_collide) {
-                    let (c, m) = self.read_collision(regs, ram);
-                    mem_accesses += m;
+                    let c = self.read_collision(regs, ram);
                     if c > self.collision {
                         self.collision = c;
                     }
-                    mem_accesses += self.write_collision(regs, ram, sprcoll & SPRCOLL_NUMBER);
+                    self.write_collision(regs, ram, sprcoll & SPRCOLL_NUMBER);
                 }
             }
 
             _ => (),    
         }
-        mem_accesses
     }
 
 
     pub fn render_sprites(&mut self, regs: &mut SuzyRegisters, ram: &mut Ram) -> bool {
+        if !self.fast {
+            self.render_step(regs, ram);
+            self.apply_dma_ticks(regs);
+            return regs.task() == SuzyTask::None;
+        }
+
+        /* Runs the steps up to the end of the current pixel line at once, then holds the bus for as long
+        as they would have lasted one by one. The end of the sprite is left to its own step so that it
+        happens at the same tick. */
+        let mut ticks: u16 = 0;
+        self.batch.clear();
+        loop {
+            self.render_step(regs, ram);
+            self.batch.push((ticks, self.dma.first_page_read()));
+            ticks = ticks.saturating_add(self.dma.take_ticks().max(1));
+            if regs.task() != SuzyTask::SpriteGo || !matches!(regs.task_step(),
+                TaskStep::InitializePainting | TaskStep::LoadSCB | TaskStep::InitializeQuadrants |
+                TaskStep::InitializeQuadrant | TaskStep::RenderLinesStart | TaskStep::RenderPixelHeightStart |
+                TaskStep::RenderPixelsInLine | TaskStep::RenderLinesEnd | TaskStep::NextQuadrant) {
+                break;
+            }
+        }
+        self.batch_delay = ticks - 1;
+        regs.set_task_ticks_delay(self.batch_delay);
+        regs.task() == SuzyTask::None
+    }
+
+    fn render_step(&mut self, regs: &mut SuzyRegisters, ram: &mut Ram) {
         match regs.task_step() {
             TaskStep::None => (),
             TaskStep::InitializePainting      => self.initialize_for_painting(regs),
@@ -653,7 +680,46 @@ impl Renderer {
             TaskStep::SpriteEnd               => regs.set_task(SuzyTask::EndSprite),
             _                                 => self.stop_sprite_engine(regs),
         }
-        regs.task() == SuzyTask::None
+    }
+
+    /* The step itself lasts a tick, the rest of its RAM cycles and work delay the next one. */
+    fn apply_dma_ticks(&mut self, regs: &mut SuzyRegisters) {
+        let ticks = self.dma.take_ticks();
+        regs.set_task_ticks_delay(ticks.saturating_sub(1));
+    }
+
+    /* Renders a pixel line per call instead of a step. */
+    pub fn fast(&self) -> bool {
+        self.fast
+    }
+
+    pub fn set_fast(&mut self, fast: bool) {
+        self.fast = fast;
+        self.batch.clear();
+    }
+
+    /* In fast mode the page is closed in the middle of a batch already rendered: the first of its steps
+    that wouldn't have started yet and reads RAM pays a normal cycle instead of a page one, the rest of
+    the batch is delayed by as much. */
+    pub fn close_dma_page(&mut self, regs: &mut SuzyRegisters) {
+        let next = (self.batch_delay + 1).saturating_sub(regs.task_ticks_delay());
+        match self.batch.iter().position(|&(start, first)| start >= next && first.is_some()) {
+            None => self.dma.close_page(),
+            Some(i) if self.batch[i].1 == Some(true) => {
+                let ticks = self.dma.miss_page();
+                self.batch[i].1 = Some(false);
+                for step in &mut self.batch[i + 1..] {
+                    step.0 += ticks;
+                }
+                self.batch_delay += ticks;
+                regs.set_task_ticks_delay(regs.task_ticks_delay() + ticks);
+            }
+            Some(_) => (),
+        }
+    }
+
+    pub fn dma_ticks(&self) -> u64 {
+        self.dma.total_ticks()
     }
 
     pub fn push_sprite_data(&mut self, data: u8) {
@@ -673,4 +739,368 @@ impl Default for Renderer {
     fn default() -> Self {
         Renderer::new()
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+    use alloc::vec::Vec;
+    use super::*;
+
+    const SCB_ADDR: u16 = 0x0200;
+    const DATA_ADDR: u16 = 0x0400;
+    const VIDBAS: u16 = 0x2000;
+    const COLLBAS: u16 = 0x6000;
+    const COLLOFF: u16 = 0x0030;
+    const SCREEN_BYTES: u16 = 0x1FE0;
+    const BG: u8 = 0x55;
+    const COLL_BG: u8 = 0x22;
+    const PENS: [u8; 8] = [0x01, 0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF];
+
+    type Rows = &'static [&'static str];
+
+    struct Scb {
+        ctl0: u8,
+        ctl1: u8,
+        coll: u8,
+        next: u16,
+        data: u16,
+        hpos: i16,
+        vpos: i16,
+        hsize: u16,
+        vsize: u16,
+        stretch: i16,
+        tilt: i16,
+        pens: [u8; 8],
+    }
+
+    impl Scb {
+        /* Normal 4 bpp sprite drawing packed data at `DATA_ADDR` with one pixel per source pixel. */
+        fn new(hpos: i16, vpos: i16) -> Self {
+            Self {
+                ctl0: SPRCTL0_BPP | 4,
+                ctl1: SPRCTL1_RELOAD_HV,
+                coll: 0x03,
+                next: 0,
+                data: DATA_ADDR,
+                hpos,
+                vpos,
+                hsize: 0x0100,
+                vsize: 0x0100,
+                stretch: 0,
+                tilt: 0,
+                pens: PENS,
+            }
+        }
+
+        fn bytes(&self) -> Vec<u8> {
+            let mut b = vec![self.ctl0, self.ctl1, self.coll];
+            for v in [self.next, self.data, self.hpos as u16, self.vpos as u16] {
+                b.extend_from_slice(&v.to_le_bytes());
+            }
+            let reload = self.ctl1 & SPRCTL1_RELOAD_HVST;
+            if reload >= SPRCTL1_RELOAD_HV {
+                b.extend_from_slice(&self.hsize.to_le_bytes());
+                b.extend_from_slice(&self.vsize.to_le_bytes());
+            }
+            if reload >= SPRCTL1_RELOAD_HVS {
+                b.extend_from_slice(&self.stretch.to_le_bytes());
+            }
+            if reload >= SPRCTL1_RELOAD_HVST {
+                b.extend_from_slice(&self.tilt.to_le_bytes());
+            }
+            if self.ctl1 & SPRCTL1_REUSE_PALETTE == 0 {
+                b.extend_from_slice(&self.pens);
+            }
+            b
+        }
+    }
+
+    /* Packs (value, bits) fields, most significant bit first, into a sprite data line with its offset. */
+    fn line(fields: &[(u32, u16)]) -> Vec<u8> {
+        let mut data = vec![0];
+        let mut bit = 0;
+        for &(v, bits) in fields {
+            for i in (0..bits).rev() {
+                if bit % 8 == 0 {
+                    data.push(0);
+                }
+                let last = data.len() - 1;
+                data[last] |= (((v >> i) & 1) as u8) << (7 - bit % 8);
+                bit += 1;
+            }
+        }
+        data[0] = data.len() as u8;
+        data
+    }
+
+    /* Literal line of `bpp` bits pixels. The hardware loses the last bit of a line's data, a zero byte is added
+    when the last pixel ends on a byte boundary. */
+    fn literal(pixels: &[u32], bpp: u16) -> Vec<u8> {
+        let fields: Vec<(u32, u16)> = pixels.iter().map(|&p| (p, bpp)).collect();
+        let mut l = line(&fields);
+        if (pixels.len() as u16 * bpp).is_multiple_of(8) {
+            l.push(0);
+            l[0] += 1;
+        }
+        l
+    }
+
+    /* Packed line made of (literal, pixels) packets. */
+    fn packed(packets: &[(bool, &[u32])], bpp: u16) -> Vec<u8> {
+        let mut fields = vec![];
+        for &(literal, pixels) in packets {
+            fields.push((literal as u32, 1));
+            fields.push((pixels.len() as u32 - 1, 4));
+            if literal {
+                fields.extend(pixels.iter().map(|&p| (p, bpp)));
+            } else {
+                fields.push((pixels[0], bpp));
+            }
+        }
+        fields.push((0, 5));
+        line(&fields)
+    }
+
+    fn ram() -> Ram {
+        let mut ram = Ram::new();
+        ram.fill(0);
+        for a in 0..SCREEN_BYTES {
+            ram.set(VIDBAS + a, BG);
+            ram.set(COLLBAS + a, COLL_BG);
+        }
+        ram
+    }
+
+    /* Sprite data lines, each one is followed by `1` to go to the next quadrant or `0` to end the sprite. */
+    fn load(ram: &mut Ram, scbs: &[(u16, &Scb)], lines: &[&[u8]]) {
+        for (addr, scb) in scbs {
+            ram.copy(*addr, &scb.bytes());
+        }
+        let data: Vec<u8> = lines.concat();
+        ram.copy(DATA_ADDR, &data);
+    }
+
+    fn render(ram: &mut Ram, sprsys: u8, hoff: i16, voff: i16) -> SuzyRegisters {
+        let mut regs = SuzyRegisters::new();
+        regs.set_u16(VIDBASL, VIDBAS);
+        regs.set_u16(COLLBASL, COLLBAS);
+        regs.set_u16(COLLOFFL, COLLOFF);
+        regs.set_u16(HSIZOFFL, 0x007F);
+        regs.set_u16(VSIZOFFL, 0x007F);
+        regs.set_i16(HOFFL, hoff);
+        regs.set_i16(VOFFL, voff);
+        regs.set_sprsys(sprsys);
+        regs.set_u16(SCBNEXTL, SCB_ADDR);
+        regs.set_task(SuzyTask::SpriteGo);
+        regs.set_task_step(TaskStep::InitializePainting);
+
+        let mut renderer = Renderer::new();
+        for _ in 0..100_000 {
+            match regs.task() {
+                SuzyTask::SpriteGo => if renderer.render_sprites(&mut regs, ram) {
+                    return regs;
+                }
+                SuzyTask::EndSprite => {
+                    renderer.sprite_end(&mut regs, ram);
+                    regs.set_task(SuzyTask::SpriteGo);
+                }
+                _ => return regs,
+            }
+        }
+        panic!("Sprite engine never stopped.");
+    }
+
+    fn nibbles(ram: &Ram, base: u16, x: u16, y: u16, len: u16) -> String {
+        (x..x + len).map(|x| {
+            let b = ram.get(base + y * 80 + x / 2);
+            let n = if x & 1 == 0 { b >> 4 } else { b & 0x0F };
+            char::from_digit(n as u32, 16).unwrap().to_ascii_uppercase()
+        }).collect()
+    }
+
+    fn assert_screen(ram: &Ram, x: u16, y: u16, rows: &[&str]) {
+        for (i, row) in rows.iter().enumerate() {
+            assert_eq!(&nibbles(ram, VIDBAS, x, y + i as u16, row.len() as u16), row, "row {}", y + i as u16);
+        }
+    }
+
+    fn assert_collision(ram: &Ram, x: u16, y: u16, rows: &[&str]) {
+        for (i, row) in rows.iter().enumerate() {
+            assert_eq!(&nibbles(ram, COLLBAS, x, y + i as u16, row.len() as u16), row, "row {}", y + i as u16);
+        }
+    }
+
+    #[test]
+    fn sprite_types() {
+        // (screen, collision buffer, collision depository)
+        const EXPECTED: [(&str, &str, u8); 8] = [
+            ("01EF35", "332332", 0x00),  // 0 - BACKGROUND SHADOW, no collision for shadow pixels
+            ("01EF35", "222222", 0x00),  // 1 - BACKGROUND NOCOLLIDE
+            ("515535", "232332", 0x02),  // 2 - BOUNDARY_SHADOW
+            ("51E535", "233332", 0x02),  // 3 - BOUNDARY
+            ("51EF35", "233332", 0x02),  // 4 - NORMAL
+            ("51EF35", "222222", 0x00),  // 5 - NOCOLLIDE
+            ("54BA65", "232332", 0x02),  // 6 - XOR SHADOW
+            ("51EF35", "232332", 0x02),  // 7 - SHADOW
+        ];
+        for (t, (screen, collision, depository)) in EXPECTED.iter().enumerate() {
+            let mut ram = ram();
+            let scb = Scb { ctl0: SPRCTL0_BPP | t as u8, ..Scb::new(0, 0) };
+            load(&mut ram, &[(SCB_ADDR, &scb)], &[&packed(&[(true, &[0x0, 0x1, 0xE, 0xF, 0x3])], 4), &[0]]);
+            render(&mut ram, 0, 0, 0);
+            assert_screen(&ram, 0, 0, &[screen]);
+            assert_collision(&ram, 0, 0, &[collision]);
+            assert_eq!(ram.get(SCB_ADDR + COLLOFF), *depository, "type {}", t);
+        }
+    }
+
+    #[test]
+    fn bpp() {
+        // (bpp, pixels, expected)
+        const CASES: [(u8, &[u32], &str); 4] = [
+            (0, &[1, 0, 1, 1, 0, 1], "1511515"),
+            (1, &[3, 1, 2, 0, 3], "312535"),
+            (2, &[7, 5, 2, 0, 6], "752565"),
+            (3, &[9, 0xA, 0, 0xF], "9A5F5"),
+        ];
+        for (bpp, pixels, expected) in CASES {
+            let bits = bpp as u16 + 1;
+            for (ctl1, data) in [
+                (SPRCTL1_RELOAD_HV | SPRCTL1_LITERAL, literal(pixels, bits)),
+                (SPRCTL1_RELOAD_HV, packed(&[(true, pixels)], bits)),
+            ] {
+                let mut ram = ram();
+                let scb = Scb { ctl0: (bpp << 6) | 4, ctl1, ..Scb::new(0, 0) };
+                load(&mut ram, &[(SCB_ADDR, &scb)], &[&data, &[0]]);
+                render(&mut ram, 0, 0, 0);
+                assert_screen(&ram, 0, 0, &[expected, "5555555"]);
+            }
+        }
+    }
+
+    #[test]
+    fn packed_runs() {
+        let mut ram = ram();
+        let scb = Scb::new(0, 0);
+        let line0 = packed(&[(false, &[2, 2, 2]), (true, &[1, 3]), (false, &[0, 0]), (false, &[0xF, 0xF])], 4);
+        let line1 = packed(&[(false, &[7; 16])], 4);
+        // A packed header with a count of 0 is the end of the line
+        let line2 = packed(&[(false, &[4]), (true, &[6])], 4);
+        load(&mut ram, &[(SCB_ADDR, &scb)], &[&line0, &line1, &line2, &[0]]);
+        render(&mut ram, 0, 0, 0);
+        assert_screen(&ram, 0, 0, &["2221355FF5", "77777777777777775", "55", "5"]);
+    }
+
+    #[test]
+    fn quadrants() {
+        let quadrant = |a, b| packed(&[(true, &[a, b])], 4);
+        let lines: [&[u8]; 8] = [&quadrant(1, 2), &[1], &quadrant(3, 4), &[1], &quadrant(5, 6), &[1], &quadrant(7, 8), &[0]];
+        // (ctl0, ctl1, top row, rows from x = 18)
+        const CASES: [(u8, u8, u16, [&str; 2]); 4] = [
+            (0, 0, 9, ["6534", "8712"]),
+            (0, SPRCTL1_DRAW_QUAD, 10, ["5217", "5435"]),
+            (SPRCTL0_HFLIP, 0, 9, ["5435", "5217"]),
+            (SPRCTL0_VFLIP, 0, 10, ["8712", "6534"]),
+        ];
+        for (ctl0, ctl1, y, rows) in CASES {
+            let mut ram = ram();
+            let scb = Scb { ctl0: SPRCTL0_BPP | ctl0 | 4, ctl1: SPRCTL1_RELOAD_HV | ctl1, ..Scb::new(20, 10) };
+            load(&mut ram, &[(SCB_ADDR, &scb)], &lines);
+            render(&mut ram, 0, 0, 0);
+            assert_screen(&ram, 18, y - 1, &["555555"]);
+            assert_screen(&ram, 18, y, &rows);
+            assert_screen(&ram, 18, y + 2, &["555555"]);
+        }
+    }
+
+    #[test]
+    fn stretch_and_tilt() {
+        // (ctl1, hsize, vsize, stretch, tilt, hpos, rows from x = 10)
+        const CASES: [(u8, u16, u16, i16, i16, i16, Rows); 7] = [
+            (SPRCTL1_RELOAD_HV, 0x200, 0x200, 0, 0, 10, &["11225", "11225", "55555"]),
+            (SPRCTL1_RELOAD_HV, 0x180, 0x100, 0, 0, 10, &["12255", "55555"]),
+            (SPRCTL1_RELOAD_HVS, 0x100, 0x300, 0x100, 0, 10, &["12555555", "11225555", "11122255", "55555555"]),
+            (SPRCTL1_RELOAD_HVS, 0x300, 0x300, -0x100, 0, 10, &["11122255", "11225555", "12555555", "55555555"]),
+            (SPRCTL1_RELOAD_HVST, 0x100, 0x300, 0, 0x100, 10, &["12555", "51255", "55125", "55555"]),
+            (SPRCTL1_RELOAD_HVST, 0x100, 0x400, 0, 0x80, 10, &["1255", "1255", "5125", "5125"]),
+            (SPRCTL1_RELOAD_HVST, 0x100, 0x300, 0, -0x100, 12, &["55125", "51255", "12555", "55555"]),
+        ];
+        for (ctl1, hsize, vsize, stretch, tilt, hpos, rows) in CASES {
+            let mut ram = ram();
+            let scb = Scb { ctl1, hsize, vsize, stretch, tilt, ..Scb::new(hpos, 0) };
+            load(&mut ram, &[(SCB_ADDR, &scb)], &[&packed(&[(true, &[1, 2])], 4), &[0]]);
+            render(&mut ram, 0, 0, 0);
+            assert_screen(&ram, 10, 0, rows);
+        }
+    }
+
+    #[test]
+    fn vertical_stretch() {
+        // "The vertical size of a sprite can be modified every time a scan line is processed."
+        for (sprsys, rows) in [
+            (SPRSYS_VSTRETCH, &["15555555", "22555555", "22255555", "33335555", "33333555", "33333355", "33333335", "55555555"][..]),
+            (0, &["15555555", "22555555", "33355555", "55555555"][..]),
+        ] {
+            let mut ram = ram();
+            let scb = Scb { ctl1: SPRCTL1_RELOAD_HVS, stretch: 0x100, ..Scb::new(0, 0) };
+            let lines: Vec<Vec<u8>> = (1..=3).map(|p| packed(&[(true, &[p])], 4)).collect();
+            load(&mut ram, &[(SCB_ADDR, &scb)], &[&lines[0], &lines[1], &lines[2], &[0]]);
+            render(&mut ram, sprsys, 0, 0);
+            assert_screen(&ram, 0, 0, rows);
+        }
+    }
+
+    #[test]
+    fn reload_flags() {
+        let mut ram = ram();
+        let mut pens = PENS;
+        pens[0] = 0x0A;
+        let first = Scb { next: 0x0240, hsize: 0x200, pens, ..Scb::new(0, 0) };
+        let skipped = Scb { ctl1: SPRCTL1_RELOAD_HV | SPRCTL1_SKIP_SPRITE, next: 0x0280, ..Scb::new(0, 2) };
+        let reuse = Scb { ctl1: SPRCTL1_REUSE_PALETTE, next: 0x02C0, ..Scb::new(0, 4) };
+        let last = Scb::new(0, 8);
+        load(&mut ram, &[(SCB_ADDR, &first), (0x0240, &skipped), (0x0280, &reuse), (0x02C0, &last)], &[&packed(&[(true, &[1])], 4), &[0]]);
+        render(&mut ram, 0, 0, 0);
+        /* The reused palette and size come from the first sprite, the skipped one isn't drawn. */
+        assert_screen(&ram, 0, 0, &["AA5", "555", "555", "555", "AA5", "555", "555", "555", "155", "555"]);
+    }
+
+    #[test]
+    fn clipping() {
+        let square = packed(&[(false, &[1, 1, 1])], 4);
+        // (hoff, voff, hpos, vpos, x, y, rows from x, y)
+        const CASES: [(i16, i16, i16, i16, u16, u16, Rows); 3] = [
+            (0, 0, -1, -1, 0, 0, &["115", "115", "555"]),
+            (10, 5, 12, 6, 1, 0, &["55555", "51115", "51115", "51115", "55555"]),
+            (0, 0, 158, 0, 156, 0, &["5511", "5511", "5511", "5555"]),
+        ];
+        for (hoff, voff, hpos, vpos, x, y, rows) in CASES {
+            let mut ram = ram();
+            let scb = Scb::new(hpos, vpos);
+            load(&mut ram, &[(SCB_ADDR, &scb)], &[&square, &square, &square, &[0]]);
+            render(&mut ram, 0, hoff, voff);
+            assert_screen(&ram, x, y, rows);
+        }
+
+        // The right edge doesn't wrap to the next line
+        let mut ram = ram();
+        load(&mut ram, &[(SCB_ADDR, &Scb::new(158, 0))], &[&square, &[0]]);
+        render(&mut ram, 0, 0, 0);
+        assert_screen(&ram, 0, 1, &["55"]);
+    }
+
+    #[test]
+    fn left_handed() {
+        /* Left handed mode only changes how the joystick reads, sprites are drawn the same. */
+        let quadrant = |a, b| packed(&[(true, &[a, b])], 4);
+        let lines: [&[u8]; 8] = [&quadrant(1, 2), &[1], &quadrant(3, 4), &[1], &quadrant(5, 6), &[1], &quadrant(7, 8), &[0]];
+        let scb = Scb { tilt: 0x40, stretch: 0x20, ctl1: SPRCTL1_RELOAD_HVST, vsize: 0x300, ..Scb::new(40, 40) };
+        let mut right = ram();
+        load(&mut right, &[(SCB_ADDR, &scb)], &lines);
+        let mut left = right.clone();
+        render(&mut right, 0, 0, 0);
+        render(&mut left, SPRSYS_LEFTHAND, 0, 0);
+        assert!((0..=0xFFFF).all(|a| right.get(a) == left.get(a)));
+        assert_ne!(nibbles(&right, VIDBAS, 40, 40, 1), "5");
+    }
+}
@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+/* A CPU write to a Suzy register while the sprite engine was working. */
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct UnsafeAccess {
+    pub tick: u64,
+    pub addr: u16,
+    pub data: u8,
+    /* The SCB being painted when the write happened. */
+    pub scb: u16,
+}
+
+/* Reports the unsafe accesses as they happen. They set the unsafe flag of SPRSYS on the hardware too,
+but software rarely checks it, so this is meant to track them down while debugging. */
+pub trait UnsafeAccessHook: Send {
+    fn unsafe_access(&mut self, access: UnsafeAccess);
+}
+
+impl<F: FnMut(UnsafeAccess) + Send> UnsafeAccessHook for F {
+    fn unsafe_access(&mut self, access: UnsafeAccess) {
+        self(access)
+    }
+}
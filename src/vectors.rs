@@ -5,7 +5,7 @@ use crate::{bus::{Bus, BusStatus}, consts::*};
 const VECTOR_NORMAL_READ_TICKS: i8 = 5;
 const VECTOR_NORMAL_WRITE_TICKS: i8 = 5;
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Vectors {
     data: [u8; 6],
     addr_r: u16,